@@ -34,7 +34,7 @@ pub fn derive_read_from_file_impl(input: DeriveInput) -> syn::Result<TokenStream
             Ok(quote! {
                 impl #impl_g #binary_crate::traits::ReadFromFile for #name #ty_g #wh {
                     fn read_from_file(
-                        file: &mut #binary_crate::core::File,
+                        file: &mut #binary_crate::core::Decoder<'_>,
                     ) -> #global_crate::Result<Self> {
                         Ok(Self {
                             #(
@@ -65,7 +65,7 @@ pub fn derive_read_from_file_impl(input: DeriveInput) -> syn::Result<TokenStream
                 return Ok(quote! {
                     impl #impl_g #binary_crate::traits::ReadFromFile for #name #ty_g #wh {
                         fn read_from_file(
-                            file: &mut #binary_crate::core::File,
+                            file: &mut #binary_crate::core::Decoder<'_>,
                         ) -> #global_crate::Result<Self> {
                             let __i = #repr::read_from_file(file)?;
                             match __i {
@@ -107,7 +107,7 @@ pub fn derive_read_from_file_impl(input: DeriveInput) -> syn::Result<TokenStream
             Ok(quote! {
                 impl #impl_g #binary_crate::traits::ReadFromFile for #name #ty_g #wh {
                     fn read_from_file(
-                        file: &mut #binary_crate::core::File,
+                        file: &mut #binary_crate::core::Decoder<'_>,
                     ) -> #global_crate::Result<Self> {
                         let x = #type_ident::read_from_file(file)?;
                         match x {
@@ -149,7 +149,7 @@ pub fn read_from_file_foreign_with_new_impl(input: TokenStream) -> syn::Result<T
     Ok(quote! {
         impl #binary_crate::traits::ReadFromFile for #t {
             fn read_from_file(
-                file: &mut #binary_crate::core::File,
+                file: &mut #binary_crate::core::Decoder<'_>,
             ) -> #global_crate::Result<Self> {
                 Ok(Self::new(
                     #token_stream
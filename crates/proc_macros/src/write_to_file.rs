@@ -27,13 +27,18 @@ pub fn derive_write_to_file_impl(input: DeriveInput) -> syn::Result<TokenStream>
                 impl #impl_g #binary_crate::traits::WriteToFile for #name #ty_g #wh {
                     fn write_to_file(
                         &self,
-                        file: &mut #binary_crate::core::File,
+                        file: &mut #binary_crate::core::Encoder<'_>,
                     ) -> #global_crate::Result<()> {
                         #(
                             #binary_crate::traits::WriteToFile::write_to_file(&self.#idents, file)?;
                         )*
                         Ok(())
                     }
+                    fn encoded_size(&self, interner: &#binary_crate::core::StringInterner) -> usize {
+                        0 #(
+                            + #binary_crate::traits::WriteToFile::encoded_size(&self.#idents, interner)
+                        )*
+                    }
                 }
             })
         }
@@ -51,15 +56,20 @@ pub fn derive_write_to_file_impl(input: DeriveInput) -> syn::Result<TokenStream>
                     impl #impl_g #binary_crate::traits::WriteToFile for #name #ty_g #wh {
                         fn write_to_file(
                             &self,
-                            file: &mut #binary_crate::core::File,
+                            file: &mut #binary_crate::core::Encoder<'_>,
                         ) -> #global_crate::Result<()> {
                             let __i = *self as #repr;
                             __i.write_to_file(file)
                         }
+                        fn encoded_size(&self, interner: &#binary_crate::core::StringInterner) -> usize {
+                            let __i = *self as #repr;
+                            #binary_crate::traits::WriteToFile::encoded_size(&__i, interner)
+                        }
                     }
                 });
             }
             let mut ts = TokenStream::new();
+            let mut size_ts = TokenStream::new();
             for v in variants {
                 let mut is_unnamed = false;
                 let f_idents = v
@@ -104,12 +114,19 @@ pub fn derive_write_to_file_impl(input: DeriveInput) -> syn::Result<TokenStream>
                         )*
                     }
                 });
+                size_ts.extend(quote! {
+                    #matcher => {
+                        0 #(
+                            + #binary_crate::traits::WriteToFile::encoded_size(#out_idents, interner)
+                        )*
+                    }
+                });
             }
             Ok(quote! {
                 impl #impl_g #binary_crate::traits::WriteToFile for #name #ty_g #wh {
                     fn write_to_file(
                         &self,
-                        file: &mut #binary_crate::core::File,
+                        file: &mut #binary_crate::core::Encoder<'_>,
                     ) -> #global_crate::Result<()> {
                         self.to_type().write_to_file(file)?;
                         match self {
@@ -117,6 +134,12 @@ pub fn derive_write_to_file_impl(input: DeriveInput) -> syn::Result<TokenStream>
                         }
                         Ok(())
                     }
+                    fn encoded_size(&self, interner: &#binary_crate::core::StringInterner) -> usize {
+                        #binary_crate::traits::WriteToFile::encoded_size(&self.to_type(), interner)
+                            + match self {
+                                #size_ts
+                            }
+                    }
                 }
             })
         }
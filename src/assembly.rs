@@ -1,27 +1,42 @@
 use crate::Error;
-use crate::core::File;
+use crate::core::{Decoder, Encoder, File, StringInterner};
 use crate::implement::Implementation;
 use crate::traits::{ReadFromFile, WriteToFile};
-use crate::ty::TypeDef;
-use global::StringName;
+use crate::ty::assembly_ref::{AssemblyNames, AssemblyRef};
+use crate::ty::attribute::{AttributeTarget, CustomAttribute};
+use crate::ty::constant::ConstantValue;
+use crate::ty::delegate::DelegateDef;
+use crate::ty::layout::{LayoutKind, POINTER_SIZE, core_value_size, is_core_reference_type};
+use crate::ty::method::Method;
+use crate::ty::nesting::NestedVisibility;
+use crate::ty::signature::check_method_keys;
+use crate::ty::r#struct::StructDef;
+use crate::ty::{CORE_ASSEMBLY, TypeDef, split_type_ref};
+use crate::validation::{ValidationError, ValidationWarning};
+use global::{StringName, StringTypeReference};
 use proc_macros::{ReadFromFile, WriteToFile};
 use std::collections::HashMap;
 use std::path::Path;
 
-#[derive(Default, Debug, Clone, ReadFromFile, WriteToFile)]
+/// Equality compares the `HashMap` tables by content, regardless of insertion order.
+#[derive(Default, Debug, Clone, PartialEq, Eq)]
 pub struct Assembly {
     name: StringName,
     type_defs: HashMap<StringName, TypeDef>,
     implementations: HashMap<StringName, Implementation>,
+    custom_attributes: Vec<CustomAttribute>,
+    /// Only the [pinned](AssemblyRef::is_pinned) rows of the dependency table, sorted by
+    /// name; the others follow from the type references.
+    pinned_assembly_refs: Vec<AssemblyRef>,
 }
 
 #[allow(unused)]
 const MAGIC: [u8; 2] = *b"PL";
 
 #[allow(unused)]
-#[derive(Debug, Default, Clone, ReadFromFile, WriteToFile)]
-struct Header {
-    magic: [u8; 2],
+#[derive(Debug, Default, Clone, PartialEq, Eq, ReadFromFile, WriteToFile)]
+pub(crate) struct Header {
+    pub(crate) magic: [u8; 2],
 }
 
 #[allow(unused)]
@@ -53,12 +68,660 @@ impl Assembly {
     pub fn implementations_mut(&mut self) -> &mut HashMap<StringName, Implementation> {
         &mut self.implementations
     }
+    /// Reads the constant of field `field` on type `ty` straight from metadata.
+    pub fn field_constant(&self, ty: &StringName, field: &StringName) -> Option<&ConstantValue> {
+        self.type_defs
+            .get(ty)?
+            .fields()?
+            .get(field)?
+            .constant()
+            .as_ref()
+    }
+    pub fn custom_attributes(&self) -> &[CustomAttribute] {
+        &self.custom_attributes
+    }
+    pub fn custom_attributes_mut(&mut self) -> &mut Vec<CustomAttribute> {
+        &mut self.custom_attributes
+    }
+    /// Every other assembly named by a type reference anywhere in this one, generic arguments
+    /// included, sorted by name.
+    pub fn referenced_assemblies(&self) -> Vec<&StringName> {
+        let mut names = AssemblyNames::default();
+        self.type_defs
+            .values()
+            .for_each(|type_def| names.add_type_def(type_def));
+        self.implementations
+            .values()
+            .for_each(|implementation| names.add_implementation(implementation));
+        names.add_attributes(&self.custom_attributes);
+        let mut names = names
+            .into_inner()
+            .into_iter()
+            .filter(|name| **name != self.name)
+            .collect::<Vec<_>>();
+        names.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+        names
+    }
+    /// The dependency table written with the assembly: a row for every
+    /// [referenced assembly](Self::referenced_assemblies), pinned where a pin exists and
+    /// unversioned otherwise, sorted by name. Pins for assemblies no longer referenced are
+    /// left out.
+    pub fn assembly_refs(&self) -> Vec<AssemblyRef> {
+        self.referenced_assemblies()
+            .into_iter()
+            .map(|name| {
+                self.assembly_ref(name)
+                    .cloned()
+                    .unwrap_or_else(|| AssemblyRef::unversioned(name.clone()))
+            })
+            .collect()
+    }
+    /// The pinned row for `name`, if any.
+    pub fn assembly_ref(&self, name: &StringName) -> Option<&AssemblyRef> {
+        self.pinned_assembly_refs
+            .iter()
+            .find(|assembly_ref| assembly_ref.name() == name)
+    }
+    /// Pins the version or identity of a dependency, replacing any earlier row for the same
+    /// assembly. Rows that are not [pinned](AssemblyRef::is_pinned) only remove the earlier
+    /// one. Pinning an assembly that is not [referenced](Self::referenced_assemblies) fails.
+    pub fn pin_assembly_ref(&mut self, assembly_ref: AssemblyRef) -> Result<(), ValidationError> {
+        if assembly_ref.is_pinned() && !self.referenced_assemblies().contains(&assembly_ref.name())
+        {
+            return Err(ValidationError::UnreferencedAssembly {
+                assembly: assembly_ref.name().clone(),
+            });
+        }
+        let position = self
+            .pinned_assembly_refs
+            .binary_search_by(|pinned| pinned.name().as_str().cmp(assembly_ref.name().as_str()));
+        match (position, assembly_ref.is_pinned()) {
+            (Ok(index), true) => self.pinned_assembly_refs[index] = assembly_ref,
+            (Ok(index), false) => {
+                self.pinned_assembly_refs.remove(index);
+            }
+            (Err(index), true) => self.pinned_assembly_refs.insert(index, assembly_ref),
+            (Err(_), false) => {}
+        }
+        Ok(())
+    }
+    /// Every item carrying an attribute of type `attr_type`, in no particular order.
+    pub fn items_with_attribute(
+        &self,
+        attr_type: &StringTypeReference,
+    ) -> Vec<AttributeTarget<'_>> {
+        let has =
+            |attrs: &[CustomAttribute]| attrs.iter().any(|attr| attr.attr_type().eq(attr_type));
+        let mut found = Vec::new();
+        if has(&self.custom_attributes) {
+            found.push(AttributeTarget::Assembly);
+        }
+        for (ty, type_def) in &self.type_defs {
+            if has(type_def.custom_attributes()) {
+                found.push(AttributeTarget::Type(ty));
+            }
+            for (method_name, method) in type_def.methods().into_iter().flatten() {
+                if has(method.custom_attributes()) {
+                    found.push(AttributeTarget::Method {
+                        ty,
+                        method: method_name,
+                    });
+                }
+                for (index, param) in method.params().iter().enumerate() {
+                    if has(param.custom_attributes()) {
+                        found.push(AttributeTarget::Parameter {
+                            ty,
+                            method: method_name,
+                            index,
+                        });
+                    }
+                }
+            }
+            for (field_name, field) in type_def.fields().into_iter().flatten() {
+                if has(field.custom_attributes()) {
+                    found.push(AttributeTarget::Field {
+                        ty,
+                        field: field_name,
+                    });
+                }
+            }
+            for (property_name, property) in type_def.properties().into_iter().flatten() {
+                if has(property.custom_attributes()) {
+                    found.push(AttributeTarget::Property {
+                        ty,
+                        property: property_name,
+                    });
+                }
+            }
+            for (event_name, event) in type_def.events().into_iter().flatten() {
+                if has(event.custom_attributes()) {
+                    found.push(AttributeTarget::Event {
+                        ty,
+                        event: event_name,
+                    });
+                }
+            }
+        }
+        for (implementation, block) in &self.implementations {
+            for (method_name, method) in block.methods() {
+                if has(method.custom_attributes()) {
+                    found.push(AttributeTarget::ImplementationMethod {
+                        implementation,
+                        method: method_name,
+                    });
+                }
+                for (index, param) in method.params().iter().enumerate() {
+                    if has(param.custom_attributes()) {
+                        found.push(AttributeTarget::ImplementationParameter {
+                            implementation,
+                            method: method_name,
+                            index,
+                        });
+                    }
+                }
+            }
+        }
+        found
+    }
+    pub fn enclosing_type(&self, ty: &StringName) -> Option<&StringName> {
+        Some(self.type_defs.get(ty)?.nesting()?.enclosing())
+    }
+    /// Types declared directly inside `outer`, in no particular order.
+    pub fn nested_types<'a>(
+        &'a self,
+        outer: &'a StringName,
+    ) -> impl Iterator<Item = (&'a StringName, &'a TypeDef)> {
+        self.type_defs.iter().filter(move |(_, type_def)| {
+            type_def
+                .nesting()
+                .is_some_and(|nesting| nesting.enclosing().eq(outer))
+        })
+    }
+    /// `ty` followed by its enclosing types, innermost first. Stops after `type_defs.len()`
+    /// steps so that a nesting cycle, which [`Assembly::validate`] rejects but a loaded file
+    /// may still contain, cannot loop forever.
+    fn enclosing_chain<'a>(&'a self, ty: &'a StringName) -> impl Iterator<Item = &'a StringName> {
+        std::iter::successors(Some(ty), |name| self.enclosing_type(name))
+            .take(self.type_defs.len() + 1)
+    }
+    /// Whether `ty` is `outer` or declared, at any depth, inside it.
+    pub fn is_within(&self, ty: &StringName, outer: &StringName) -> bool {
+        self.enclosing_chain(ty).any(|name| name == outer)
+    }
+    /// Applies [`NestedVisibility`] along the enclosing chain of `ty`, as seen from the
+    /// type `from` in this assembly.
+    pub fn is_accessible_from(&self, ty: &StringName, from: &StringName) -> bool {
+        self.enclosing_chain(ty)
+            .filter_map(|name| self.type_defs.get(name)?.nesting())
+            .all(|nesting| {
+                nesting.visibility() != NestedVisibility::Private
+                    || self.is_within(from, nesting.enclosing())
+            })
+    }
+    /// Implementation blocks attached to the type `ty` of this assembly.
+    pub fn implementations_of<'a>(
+        &'a self,
+        ty: &'a StringName,
+    ) -> impl Iterator<Item = &'a Implementation> {
+        self.implementations
+            .values()
+            .filter(move |implementation| self.local_name(implementation.ty()) == Some(ty))
+    }
+    /// Whether the type `ty` implements `interface`, directly or through the base classes and
+    /// extended interfaces that are defined in this assembly.
+    pub fn implements(&self, ty: &StringName, interface: &StringTypeReference) -> bool {
+        let mut pending = vec![ty];
+        let mut visited = Vec::new();
+        while let Some(name) = pending.pop() {
+            if visited.contains(&name) {
+                continue;
+            }
+            visited.push(name);
+            let Some(type_def) = self.type_defs.get(name) else {
+                continue;
+            };
+            if type_def.interfaces().contains(interface) {
+                return true;
+            }
+            pending.extend(
+                type_def
+                    .interfaces()
+                    .iter()
+                    .chain(type_def.parent())
+                    .filter_map(|ty| self.local_name(ty)),
+            );
+        }
+        false
+    }
+    pub fn delegates(&self) -> impl Iterator<Item = (&StringName, &DelegateDef)> {
+        self.type_defs
+            .iter()
+            .filter_map(|(name, type_def)| Some((name, type_def.as_delegate()?)))
+    }
+}
+
+impl Assembly {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (ty, type_def) in &self.type_defs {
+            self.validate_nesting(ty, type_def)?;
+            self.validate_interfaces(ty, type_def)?;
+            match type_def {
+                TypeDef::Class(class_def) => class_def.validate()?,
+                TypeDef::Struct(struct_def) => {
+                    struct_def.validate()?;
+                    self.validate_struct_layout(struct_def)?;
+                }
+                TypeDef::Enum(enum_def) => enum_def.validate()?,
+                TypeDef::Interface(interface_def) => interface_def.validate()?,
+                TypeDef::Delegate(delegate_def) => delegate_def.validate()?,
+            }
+        }
+        for (key, implementation) in &self.implementations {
+            implementation
+                .methods()
+                .values()
+                .try_for_each(Method::validate)?;
+            self.validate_implementation(key, implementation)?;
+        }
+        Ok(())
+    }
+    /// Register counts that [`Assembly::validate`] accepts but that look mistaken, for every
+    /// method of every type and implementation, and explicit-layout fields whose overlaps
+    /// could not be checked, in no particular order.
+    pub fn warnings(&self) -> Vec<ValidationWarning> {
+        let type_methods = self
+            .type_defs
+            .values()
+            .flat_map(|type_def| type_def.methods().into_iter().flatten());
+        let implementation_methods = self
+            .implementations
+            .values()
+            .flat_map(|implementation| implementation.methods());
+        let layout_warnings = self
+            .type_defs
+            .values()
+            .filter_map(|type_def| match type_def {
+                TypeDef::Struct(struct_def) => Some(self.layout_warnings(struct_def)),
+                _ => None,
+            })
+            .flatten();
+        type_methods
+            .chain(implementation_methods)
+            .filter_map(|(_, method)| method.register_warning())
+            .chain(layout_warnings)
+            .collect()
+    }
+    /// Checks that the methods of every type are stored under the keys their
+    /// [`MethodSignature`](crate::signature::MethodSignature)s give. Separate from
+    /// [`Assembly::validate`] because keys written by hand may follow older conventions.
+    pub fn validate_method_keys(&self) -> Result<(), ValidationError> {
+        for (ty, type_def) in &self.type_defs {
+            if let Some(methods) = type_def.methods() {
+                check_method_keys(ty, methods)?;
+            }
+        }
+        Ok(())
+    }
+    /// The definition `ty` refers to if it names a type of this assembly; `Ok(None)` for
+    /// types of other assemblies, which cannot be checked here.
+    fn local_type_def(
+        &self,
+        ty: &StringTypeReference,
+    ) -> Result<Option<&TypeDef>, ValidationError> {
+        match self.local_name(ty) {
+            Some(name) => self
+                .type_defs
+                .get(name)
+                .map(Some)
+                .ok_or_else(|| ValidationError::UnknownType { ty: ty.clone() }),
+            None => Ok(None),
+        }
+    }
+    /// The name `ty` has in `type_defs` if it refers to a type of this assembly.
+    fn local_name<'a>(&self, ty: &'a StringTypeReference) -> Option<&'a StringName> {
+        split_type_ref(ty)
+            .filter(|(assem, _)| **assem == self.name)
+            .map(|(_, name)| name)
+    }
+    /// Checks that each interface declared by `ty` is an interface and, when it has abstract
+    /// methods, is backed by an [`Implementation`] on the type or one of its base classes.
+    fn validate_interfaces(
+        &self,
+        ty: &StringName,
+        type_def: &TypeDef,
+    ) -> Result<(), ValidationError> {
+        if !matches!(type_def, TypeDef::Class(_) | TypeDef::Struct(_)) {
+            return Ok(());
+        }
+        for interface in type_def.interfaces() {
+            let interface_def = match self.local_type_def(interface)? {
+                Some(TypeDef::Interface(interface_def)) => interface_def,
+                Some(_) => {
+                    return Err(ValidationError::NotAnInterface {
+                        ty: interface.clone(),
+                    });
+                }
+                None => continue,
+            };
+            if !self.has_implementation(ty, interface)
+                && interface_def.abstract_methods().next().is_some()
+            {
+                return Err(ValidationError::MissingInterfaceImplementation {
+                    ty: ty.clone(),
+                    interface: interface.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+    /// Whether an [`Implementation`] of `interface` is attached to `ty` or to one of its base
+    /// classes in this assembly. The walk is bounded like [`Assembly::is_within`].
+    fn has_implementation(&self, ty: &StringName, interface: &StringTypeReference) -> bool {
+        std::iter::successors(Some(ty), |name| {
+            self.local_name(self.type_defs.get(*name)?.parent()?)
+        })
+        .take(self.type_defs.len() + 1)
+        .any(|name| {
+            self.implementations_of(name)
+                .any(|implementation| implementation.interface().as_ref() == Some(interface))
+        })
+    }
+    /// How a value of type `ty` is stored inline; `None` when that cannot be determined,
+    /// i.e. for type variables, types of other assemblies, core types of unknown kind, structs
+    /// whose size the runtime picks and structs that contain themselves. `visiting` holds
+    /// the structs being measured further out.
+    fn field_storage<'a>(
+        &'a self,
+        ty: &StringTypeReference,
+        visiting: &mut Vec<&'a StringName>,
+    ) -> Option<Storage> {
+        let (assem, name) = split_type_ref(ty)?;
+        if assem.as_str() == CORE_ASSEMBLY {
+            return match core_value_size(name.as_str()) {
+                Some(size) => Some(Storage::value(size)),
+                None => is_core_reference_type(name.as_str()).then_some(Storage::REFERENCE),
+            };
+        }
+        if *assem != self.name {
+            return None;
+        }
+        match self.type_defs.get(name)? {
+            TypeDef::Class(_) | TypeDef::Interface(_) | TypeDef::Delegate(_) => {
+                Some(Storage::REFERENCE)
+            }
+            TypeDef::Enum(enum_def) => Some(Storage::value(enum_def.underlying().size())),
+            TypeDef::Struct(struct_def) => self.struct_storage(struct_def, visiting),
+        }
+    }
+    fn struct_storage<'a>(
+        &'a self,
+        struct_def: &'a StructDef,
+        visiting: &mut Vec<&'a StringName>,
+    ) -> Option<Storage> {
+        if visiting.contains(&struct_def.name()) {
+            return None;
+        }
+        visiting.push(struct_def.name());
+        let fields = struct_def
+            .instance_fields()
+            .map(|field| Some((field, self.field_storage(field.ty(), visiting)?)))
+            .collect::<Option<Vec<_>>>();
+        visiting.pop();
+        let fields = fields?;
+        let layout = struct_def.layout();
+        let packing = layout
+            .packing()
+            .map_or(u32::MAX, |packing| u32::from(packing).max(1));
+        let align = fields
+            .iter()
+            .map(|(_, storage)| storage.align.min(packing))
+            .max()
+            .unwrap_or(1);
+        let size = match (layout.size(), layout.kind()) {
+            (Some(size), _) => size,
+            (None, LayoutKind::Explicit) => {
+                fields.iter().try_fold(0, |size: u32, (field, storage)| {
+                    Some(size.max(field.offset()?.checked_add(storage.size)?))
+                })?
+            }
+            (None, LayoutKind::Sequential) => fields
+                .iter()
+                .try_fold(0, |offset: u32, (_, storage)| {
+                    offset
+                        .checked_next_multiple_of(storage.align.min(packing))?
+                        .checked_add(storage.size)
+                })?
+                .checked_next_multiple_of(align)?,
+            (None, LayoutKind::Auto) => return None,
+        };
+        Some(Storage {
+            size,
+            align,
+            has_ref: fields.iter().any(|(_, storage)| storage.has_ref),
+        })
+    }
+    /// Explicit-layout fields that [`Assembly::validate_struct_layout`] skips because their
+    /// storage cannot be determined.
+    fn layout_warnings<'a>(
+        &'a self,
+        struct_def: &'a StructDef,
+    ) -> impl Iterator<Item = ValidationWarning> + 'a {
+        let explicit = struct_def.layout().kind() == LayoutKind::Explicit;
+        struct_def
+            .instance_fields()
+            .filter(move |field| explicit && field.offset().is_some())
+            .filter(|field| {
+                self.field_storage(field.ty(), &mut vec![struct_def.name()])
+                    .is_none()
+            })
+            .map(|field| ValidationWarning::UnknownFieldLayout {
+                ty: struct_def.name().clone(),
+                field: field.name().clone(),
+            })
+    }
+    /// Rejects explicit layouts where a field holding a reference shares bytes with another
+    /// field or where a field extends past the declared size. Fields whose storage cannot be
+    /// determined are treated as opaque and skipped; see [`Assembly::warnings`].
+    fn validate_struct_layout(&self, struct_def: &StructDef) -> Result<(), ValidationError> {
+        let layout = struct_def.layout();
+        if layout.kind() != LayoutKind::Explicit {
+            return Ok(());
+        }
+        let mut fields = Vec::new();
+        // Missing offsets were already reported by `StructDef::validate`.
+        for field in struct_def.instance_fields() {
+            let Some(start) = field.offset() else {
+                continue;
+            };
+            let Some(storage) = self.field_storage(field.ty(), &mut vec![struct_def.name()]) else {
+                continue;
+            };
+            let end = u64::from(start) + u64::from(storage.size);
+            if let Some(size) = layout.size()
+                && end > u64::from(size)
+            {
+                return Err(ValidationError::FieldExceedsStructSize {
+                    ty: struct_def.name().clone(),
+                    field: field.name().clone(),
+                    end,
+                    size,
+                });
+            }
+            fields.push((field, u64::from(start)..end, storage.has_ref));
+        }
+        for (index, (field, range, is_ref)) in fields.iter().enumerate() {
+            for (other, other_range, other_is_ref) in &fields[..index] {
+                let overlaps = range.start < other_range.end && other_range.start < range.end;
+                if overlaps && (*is_ref || *other_is_ref) {
+                    let (field, other) = if *is_ref {
+                        (field, other)
+                    } else {
+                        (other, field)
+                    };
+                    return Err(ValidationError::OverlappingReferenceField {
+                        ty: struct_def.name().clone(),
+                        field: field.name().clone(),
+                        other: other.name().clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+    fn validate_implementation(
+        &self,
+        key: &StringName,
+        implementation: &Implementation,
+    ) -> Result<(), ValidationError> {
+        let type_def = self.local_type_def(implementation.ty())?;
+        let Some(interface) = implementation.interface() else {
+            return Ok(());
+        };
+        let interface_def = match self.local_type_def(interface)? {
+            Some(TypeDef::Interface(interface_def)) => Some(interface_def),
+            Some(_) => {
+                return Err(ValidationError::NotAnInterface {
+                    ty: interface.clone(),
+                });
+            }
+            None => None,
+        };
+        if type_def.is_some_and(|type_def| !type_def.interfaces().contains(interface)) {
+            return Err(ValidationError::UndeclaredInterfaceImplementation {
+                implementation: key.clone(),
+                interface: interface.clone(),
+            });
+        }
+        let Some(interface_def) = interface_def else {
+            return Ok(());
+        };
+        match interface_def
+            .abstract_methods()
+            .find(|(name, _)| !implementation.methods().contains_key(*name))
+        {
+            Some((method, _)) => Err(ValidationError::MissingInterfaceMethod {
+                implementation: key.clone(),
+                method: method.clone(),
+            }),
+            None => Ok(()),
+        }
+    }
+    fn validate_nesting(&self, ty: &StringName, type_def: &TypeDef) -> Result<(), ValidationError> {
+        let Some(nesting) = type_def.nesting() else {
+            return Ok(());
+        };
+        let enclosing = nesting.enclosing();
+        match self.type_defs.get(enclosing) {
+            None => Err(ValidationError::UnknownEnclosingType {
+                ty: ty.clone(),
+                enclosing: enclosing.clone(),
+            }),
+            Some(outer) if !outer.can_nest() => Err(ValidationError::EnclosingTypeCannotNest {
+                ty: ty.clone(),
+                enclosing: enclosing.clone(),
+            }),
+            // The walk is bounded because a cycle further out is reported for its own
+            // members instead.
+            Some(_) => {
+                let mut current = enclosing;
+                for _ in 0..self.type_defs.len() {
+                    if current == ty {
+                        return Err(ValidationError::NestingCycle { ty: ty.clone() });
+                    }
+                    match self.enclosing_type(current) {
+                        Some(next) => current = next,
+                        None => break,
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Sorts the dependency table by name and rejects it if it names an assembly twice or one
+/// the type references do not name. The unpinned rows are the ones
+/// [`Assembly::assembly_refs`] fills in, so only the pinned ones are stored.
+impl ReadFromFile for Assembly {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        let name = ReadFromFile::read_from_file(file)?;
+        let type_defs = ReadFromFile::read_from_file(file)?;
+        let implementations = ReadFromFile::read_from_file(file)?;
+        let custom_attributes = ReadFromFile::read_from_file(file)?;
+        let mut assembly_refs = Vec::<AssemblyRef>::read_from_file(file)?;
+        assembly_refs.sort_by(|a, b| a.name().as_str().cmp(b.name().as_str()));
+        if assembly_refs
+            .windows(2)
+            .any(|pair| pair[0].name() == pair[1].name())
+        {
+            return Err(Error::WrongFileFormat.into());
+        }
+        let mut assembly = Self {
+            name,
+            type_defs,
+            implementations,
+            custom_attributes,
+            pinned_assembly_refs: Vec::new(),
+        };
+        let referenced = assembly.referenced_assemblies();
+        if assembly_refs
+            .iter()
+            .any(|assembly_ref| !referenced.contains(&assembly_ref.name()))
+        {
+            return Err(Error::WrongFileFormat.into());
+        }
+        assembly_refs.retain(AssemblyRef::is_pinned);
+        assembly.pinned_assembly_refs = assembly_refs;
+        Ok(assembly)
+    }
+}
+
+/// Writes the full table of [`Assembly::assembly_refs`] after the other tables.
+impl WriteToFile for Assembly {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        self.name.write_to_file(file)?;
+        self.type_defs.write_to_file(file)?;
+        self.implementations.write_to_file(file)?;
+        self.custom_attributes.write_to_file(file)?;
+        self.assembly_refs().write_to_file(file)
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.name.encoded_size(interner)
+            + self.type_defs.encoded_size(interner)
+            + self.implementations.encoded_size(interner)
+            + self.custom_attributes.encoded_size(interner)
+            + self.assembly_refs().encoded_size(interner)
+    }
+}
+
+/// Size, alignment and reference-ness of a value stored inline in a struct.
+#[derive(Clone, Copy)]
+struct Storage {
+    size: u32,
+    align: u32,
+    /// Whether the value is, or contains, a reference the garbage collector must see.
+    has_ref: bool,
+}
+
+impl Storage {
+    const REFERENCE: Self = Self {
+        size: POINTER_SIZE,
+        align: POINTER_SIZE,
+        has_ref: true,
+    };
+    fn value(size: u32) -> Self {
+        Self {
+            size,
+            align: size.max(1),
+            has_ref: false,
+        }
+    }
 }
 
 impl Assembly {
     pub fn from_bytes<T: AsRef<[u8]>>(bytes: T) -> global::Result<Self> {
         let mut file = File::new(bytes)?;
-        Self::read_from_file(&mut file)
+        Self::read_from_file(&mut file.decoder())
     }
     pub fn from_file<P: AsRef<Path>>(p: P) -> global::Result<Self> {
         Self::from_bytes(std::fs::read(p)?)
@@ -66,7 +729,7 @@ impl Assembly {
 
     pub fn to_file_bytes(&self) -> global::Result<Vec<u8>> {
         let mut file = File::default();
-        self.write_to_file(&mut file)?;
+        self.write_to_file(&mut file.encoder())?;
         file.to_bytes()
     }
 }
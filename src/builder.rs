@@ -0,0 +1,423 @@
+use crate::assembly::Assembly;
+use crate::implement::Implementation;
+use crate::ty::GenericBinding;
+use crate::ty::TypeDef;
+use crate::ty::class::{ClassDef, Field};
+use crate::ty::interface::InterfaceDef;
+use crate::ty::method::Method;
+use crate::ty::overrides::SlotFlags;
+use crate::ty::parameter::Parameter;
+use crate::ty::registers::required_registers;
+use crate::ty::signature::MethodSignature;
+use crate::ty::r#struct::StructDef;
+use crate::validation::ValidationError;
+use enumflags2::BitFlags;
+use global::attrs::{
+    FieldAttr, FieldImplementationFlags, MethodAttr, MethodImplementationFlags, TypeAttr,
+    TypeSpecificAttr, Visibility,
+};
+use global::instruction::StringInstruction;
+use global::{IndexMap, StringName, StringTypeReference};
+
+/// A builder call the type being built cannot represent.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BuilderError {
+    #[error("interface `{}` cannot extend a base type", .ty.as_str())]
+    InterfaceParent { ty: StringName },
+    #[error("interface `{}` cannot declare field `{}`", .ty.as_str(), .field.as_str())]
+    InterfaceField { ty: StringName, field: StringName },
+    #[error(
+        "interface `{}` cannot carry an implementation of `{}`",
+        .ty.as_str(),
+        .interface.as_str()
+    )]
+    InterfaceImplementation {
+        ty: StringName,
+        interface: StringName,
+    },
+}
+
+/// Builds an [`Assembly`] type by type.
+///
+/// Type references are given in their string form, e.g. `"[!]System.Object"`; the first one
+/// that fails to parse, or the first [`BuilderError`], is returned by
+/// [`AssemblyBuilder::build`].
+#[derive(Default)]
+pub struct AssemblyBuilder {
+    assembly: Assembly,
+    error: Option<global::Error>,
+}
+
+impl AssemblyBuilder {
+    pub fn new(name: &str) -> Self {
+        let mut builder = Self::default();
+        *builder.assembly.name_mut() = StringName::from_string(name.to_owned());
+        builder
+    }
+    pub fn class(self, name: &str) -> TypeBuilder {
+        TypeBuilder::new(self, TypeKind::Class, name)
+    }
+    pub fn r#struct(self, name: &str) -> TypeBuilder {
+        TypeBuilder::new(self, TypeKind::Struct, name)
+    }
+    pub fn interface(self, name: &str) -> TypeBuilder {
+        TypeBuilder::new(self, TypeKind::Interface, name)
+    }
+    pub fn build(self) -> global::Result<Assembly> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.assembly),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TypeKind {
+    Class,
+    Struct,
+    Interface,
+}
+
+/// Builds one type definition; [`TypeBuilder::finish`] adds it to the assembly.
+pub struct TypeBuilder {
+    assembly: AssemblyBuilder,
+    kind: TypeKind,
+    name: StringName,
+    vis: Visibility,
+    parent: Option<StringTypeReference>,
+    interfaces: Vec<StringTypeReference>,
+    type_vars: IndexMap<StringName, GenericBinding>,
+    methods: IndexMap<StringName, Method>,
+    fields: IndexMap<StringName, Field>,
+    implementations: Vec<(StringTypeReference, IndexMap<StringName, Method>)>,
+}
+
+impl TypeBuilder {
+    fn new(assembly: AssemblyBuilder, kind: TypeKind, name: &str) -> Self {
+        Self {
+            assembly,
+            kind,
+            name: StringName::from_string(name.to_owned()),
+            vis: Visibility::Public,
+            parent: None,
+            interfaces: Vec::new(),
+            type_vars: IndexMap::new(),
+            methods: IndexMap::new(),
+            fields: IndexMap::new(),
+            implementations: Vec::new(),
+        }
+    }
+    fn parse(&mut self, repr: &str) -> Option<StringTypeReference> {
+        parse_type_ref(&mut self.assembly.error, repr)
+    }
+    /// Records `error` when the type is an interface; returns whether it was.
+    fn reject_for_interface(&mut self, error: impl FnOnce(StringName) -> BuilderError) -> bool {
+        let is_interface = self.kind == TypeKind::Interface;
+        if is_interface {
+            let error = error(self.name.clone());
+            self.assembly.error.get_or_insert(error.into());
+        }
+        is_interface
+    }
+    pub fn visibility(mut self, vis: Visibility) -> Self {
+        self.vis = vis;
+        self
+    }
+    pub fn extends(mut self, parent: &str) -> Self {
+        if !self.reject_for_interface(|ty| BuilderError::InterfaceParent { ty }) {
+            self.parent = self.parse(parent);
+        }
+        self
+    }
+    /// Declares `interface` and adds an [`Implementation`] holding the methods `build` adds,
+    /// keyed `Type:Interface`.
+    pub fn implements(
+        mut self,
+        interface: &str,
+        build: impl FnOnce(ImplementationBuilder) -> ImplementationBuilder,
+    ) -> Self {
+        let rejected = self.reject_for_interface(|ty| BuilderError::InterfaceImplementation {
+            ty,
+            interface: StringName::from_string(interface.to_owned()),
+        });
+        if rejected {
+            return self;
+        }
+        let Some(interface) = self.parse(interface) else {
+            return self;
+        };
+        let builder = build(ImplementationBuilder {
+            methods: IndexMap::new(),
+            error: None,
+        });
+        if let Some(error) = builder.error {
+            self.assembly.error.get_or_insert(error);
+        }
+        self.interfaces.push(interface.clone());
+        self.implementations.push((interface, builder.methods));
+        self
+    }
+    pub fn type_var(mut self, name: &str) -> Self {
+        self.type_vars.insert(
+            StringName::from_string(name.to_owned()),
+            GenericBinding::new(Vec::new(), None),
+        );
+        self
+    }
+    pub fn field(self, name: &str, ty: &str) -> Self {
+        self.add_field(name, ty, BitFlags::empty())
+    }
+    pub fn static_field(self, name: &str, ty: &str) -> Self {
+        self.add_field(name, ty, FieldImplementationFlags::Static.into())
+    }
+    fn add_field(
+        mut self,
+        name: &str,
+        ty: &str,
+        flags: BitFlags<FieldImplementationFlags>,
+    ) -> Self {
+        let name = StringName::from_string(name.to_owned());
+        let rejected = self.reject_for_interface(|ty| BuilderError::InterfaceField {
+            ty,
+            field: name.clone(),
+        });
+        if !rejected && let Some(ty) = self.parse(ty) {
+            let field = Field::new(name.clone(), FieldAttr::new(Visibility::Public, flags), ty);
+            self.fields.insert(name, field);
+        }
+        self
+    }
+    pub fn method(self, name: &str, build: impl FnOnce(MethodBuilder) -> MethodBuilder) -> Self {
+        self.add_method(name, BitFlags::empty(), build)
+    }
+    pub fn static_method(
+        self,
+        name: &str,
+        build: impl FnOnce(MethodBuilder) -> MethodBuilder,
+    ) -> Self {
+        self.add_method(name, MethodImplementationFlags::Static.into(), build)
+    }
+    fn add_method(
+        mut self,
+        name: &str,
+        flags: BitFlags<MethodImplementationFlags>,
+        build: impl FnOnce(MethodBuilder) -> MethodBuilder,
+    ) -> Self {
+        add_method(
+            &mut self.methods,
+            &mut self.assembly.error,
+            build(MethodBuilder::new(name, flags)),
+        );
+        self
+    }
+    /// Adds the type to the assembly under its name.
+    pub fn finish(self) -> AssemblyBuilder {
+        let Self {
+            mut assembly,
+            kind,
+            name,
+            vis,
+            parent,
+            interfaces,
+            type_vars,
+            methods,
+            fields,
+            implementations,
+        } = self;
+        let ty = StringTypeReference::Single {
+            assem: assembly.assembly.name().clone(),
+            ty: name.clone(),
+        };
+        for (interface, methods) in implementations {
+            let key = format!(
+                "{}:{}",
+                name.as_str(),
+                interface.string_name_repr().as_str()
+            );
+            assembly.assembly.implementations_mut().insert(
+                StringName::from_string(key),
+                Implementation::new(ty.clone(), Some(interface), methods),
+            );
+        }
+        let type_def = match kind {
+            TypeKind::Class => {
+                let attr = TypeAttr::new(vis, TypeSpecificAttr::Class(BitFlags::empty()));
+                let mut class_def =
+                    ClassDef::new(parent, type_vars, attr, name.clone(), methods, fields);
+                *class_def.interfaces_mut() = interfaces;
+                TypeDef::Class(class_def)
+            }
+            TypeKind::Struct => {
+                let attr = TypeAttr::new(vis, TypeSpecificAttr::Struct(Default::default()));
+                let mut struct_def =
+                    StructDef::new(parent, type_vars, attr, name.clone(), methods, fields);
+                *struct_def.interfaces_mut() = interfaces;
+                TypeDef::Struct(struct_def)
+            }
+            TypeKind::Interface => {
+                let attr = TypeAttr::new(vis, TypeSpecificAttr::Interface(Default::default()));
+                TypeDef::Interface(InterfaceDef::new(
+                    interfaces,
+                    type_vars,
+                    attr,
+                    name.clone(),
+                    methods,
+                ))
+            }
+        };
+        assembly.assembly.type_defs_mut().insert(name, type_def);
+        assembly
+    }
+}
+
+/// Collects the methods of an interface implementation.
+pub struct ImplementationBuilder {
+    methods: IndexMap<StringName, Method>,
+    error: Option<global::Error>,
+}
+
+impl ImplementationBuilder {
+    pub fn method(
+        mut self,
+        name: &str,
+        build: impl FnOnce(MethodBuilder) -> MethodBuilder,
+    ) -> Self {
+        add_method(
+            &mut self.methods,
+            &mut self.error,
+            build(MethodBuilder::new(name, BitFlags::empty())),
+        );
+        self
+    }
+}
+
+fn add_method(
+    methods: &mut IndexMap<StringName, Method>,
+    error: &mut Option<global::Error>,
+    builder: MethodBuilder,
+) {
+    match builder.build() {
+        Ok(method) => {
+            methods.insert(method.name().clone(), method);
+        }
+        Err(build_error) => {
+            error.get_or_insert(build_error);
+        }
+    }
+}
+
+/// Builds one method. Its key and stored name are the [`MethodSignature`] key, and unless
+/// [`MethodBuilder::registers`] is called the register count is the
+/// [`required_registers`] of the instructions.
+pub struct MethodBuilder {
+    name: StringName,
+    vis: Visibility,
+    flags: BitFlags<MethodImplementationFlags>,
+    params: Vec<Parameter>,
+    ret_type: StringTypeReference,
+    type_vars: IndexMap<StringName, GenericBinding>,
+    instructions: Vec<StringInstruction>,
+    registers: Option<u64>,
+    slot_flags: BitFlags<SlotFlags>,
+    error: Option<global::Error>,
+}
+
+impl MethodBuilder {
+    fn new(name: &str, flags: BitFlags<MethodImplementationFlags>) -> Self {
+        Self {
+            name: StringName::from_string(name.to_owned()),
+            vis: Visibility::Public,
+            flags,
+            params: Vec::new(),
+            ret_type: StringTypeReference::core_static_single_type("System.Void"),
+            type_vars: IndexMap::new(),
+            instructions: Vec::new(),
+            registers: None,
+            slot_flags: BitFlags::empty(),
+            error: None,
+        }
+    }
+    pub fn visibility(mut self, vis: Visibility) -> Self {
+        self.vis = vis;
+        self
+    }
+    pub fn param(mut self, name: &str, ty: &str) -> Self {
+        if let Some(ty) = parse_type_ref(&mut self.error, ty) {
+            let name = StringName::from_string(name.to_owned());
+            self.params
+                .push(Parameter::new(name, ty, BitFlags::empty(), None));
+        }
+        self
+    }
+    pub fn returns(mut self, ty: &str) -> Self {
+        if let Some(ty) = parse_type_ref(&mut self.error, ty) {
+            self.ret_type = ty;
+        }
+        self
+    }
+    pub fn type_var(mut self, name: &str) -> Self {
+        self.type_vars.insert(
+            StringName::from_string(name.to_owned()),
+            GenericBinding::new(Vec::new(), None),
+        );
+        self
+    }
+    pub fn instruction(mut self, instruction: StringInstruction) -> Self {
+        self.instructions.push(instruction);
+        self
+    }
+    pub fn instructions(
+        mut self,
+        instructions: impl IntoIterator<Item = StringInstruction>,
+    ) -> Self {
+        self.instructions.extend(instructions);
+        self
+    }
+    pub fn registers(mut self, registers: u64) -> Self {
+        self.registers = Some(registers);
+        self
+    }
+    pub fn slot_flags(mut self, slot_flags: BitFlags<SlotFlags>) -> Self {
+        self.slot_flags = slot_flags;
+        self
+    }
+    fn build(self) -> global::Result<Method> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let signature = MethodSignature::new(
+            &self.name,
+            self.type_vars.len(),
+            self.params.iter().cloned(),
+        );
+        let Some(registers) = self
+            .registers
+            .or_else(|| required_registers(&self.instructions))
+        else {
+            return Err(ValidationError::RegisterOutOfRange {
+                method: signature.key(),
+            }
+            .into());
+        };
+        let mut method = Method::with_params(
+            signature.key(),
+            MethodAttr::new(self.vis, self.flags, registers),
+            self.instructions,
+            self.ret_type,
+            self.params,
+            self.type_vars,
+        );
+        *method.slot_flags_mut() = self.slot_flags;
+        Ok(method)
+    }
+}
+
+fn parse_type_ref(error: &mut Option<global::Error>, repr: &str) -> Option<StringTypeReference> {
+    match StringTypeReference::from_string_repr(repr) {
+        Ok(ty) => Some(ty),
+        Err(parse_error) => {
+            error.get_or_insert(parse_error.into());
+            None
+        }
+    }
+}
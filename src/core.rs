@@ -17,7 +17,7 @@ impl File {
         let interner_len = u64::from_le_bytes(interner_len);
         let mut interner = vec![0u8; interner_len as usize];
         data.read_exact(&mut interner)?;
-        let interner = StringInterner::new(interner);
+        let interner = StringInterner::new(interner)?;
         let mut _data = Vec::new();
         data.read_to_end(&mut _data)?;
         Ok(Self {
@@ -31,6 +31,15 @@ impl File {
     pub fn reader(&mut self) -> &mut (impl Read + Seek) {
         &mut self.data
     }
+    pub fn encoder(&mut self) -> Encoder<'_> {
+        Encoder::new(&mut self.interner, &mut self.data)
+    }
+    pub fn decoder(&mut self) -> Decoder<'_> {
+        Decoder::new(&self.interner, &mut self.data)
+    }
+    pub fn interner(&self) -> &StringInterner {
+        &self.interner
+    }
     pub fn string_position_of(&mut self, s: &str) -> global::Result<u64, GenericError<Error>> {
         self.interner.position_of(s)
     }
@@ -47,6 +56,48 @@ impl File {
     }
 }
 
+/// Write side of the binary format: the string heap being built plus any byte sink.
+pub struct Encoder<'a> {
+    interner: &'a mut StringInterner,
+    writer: &'a mut dyn Write,
+}
+
+impl<'a> Encoder<'a> {
+    pub fn new(interner: &'a mut StringInterner, writer: &'a mut dyn Write) -> Self {
+        Self { interner, writer }
+    }
+    pub fn writer(&mut self) -> &mut dyn Write {
+        self.writer
+    }
+    pub fn interner(&self) -> &StringInterner {
+        self.interner
+    }
+    pub fn string_position_of(&mut self, s: &str) -> global::Result<u64, GenericError<Error>> {
+        self.interner.position_of(s)
+    }
+}
+
+/// Read side of the binary format: a populated string heap plus any byte source.
+pub struct Decoder<'a> {
+    interner: &'a StringInterner,
+    reader: &'a mut dyn Read,
+}
+
+impl<'a> Decoder<'a> {
+    pub fn new(interner: &'a StringInterner, reader: &'a mut dyn Read) -> Self {
+        Self { interner, reader }
+    }
+    pub fn reader(&mut self) -> &mut dyn Read {
+        self.reader
+    }
+    pub fn interner(&self) -> &StringInterner {
+        self.interner
+    }
+    pub fn get_string(&self, i: u64) -> global::Result<&str, GenericError<Error>> {
+        self.interner.get(i)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct StringInterner {
     set: IndexSet<String>,
@@ -61,17 +112,28 @@ impl Default for StringInterner {
 }
 
 impl StringInterner {
-    pub fn new<T: AsRef<[u8]>>(bytes: T) -> Self {
-        let bytes = bytes.as_ref();
+    /// Reads the heap written by [`StringInterner::to_bytes`]. Every string is prefixed with
+    /// its length in bytes as a little-endian `u64`, so strings may contain NUL.
+    pub fn new<T: AsRef<[u8]>>(bytes: T) -> global::Result<Self, GenericError<Error>> {
+        let mut bytes = bytes.as_ref();
         let mut set = IndexSet::new();
-        if !bytes.starts_with(b"\0") {
-            set.insert("".to_owned());
-        }
-        for s in bytes.split(|x| x.eq(&b'\0')) {
+        while let Some((len, rest)) = bytes.split_first_chunk::<8>() {
+            let len = usize::try_from(u64::from_le_bytes(*len))
+                .ok()
+                .filter(|len| *len <= rest.len())
+                .ok_or(Error::WrongFileFormat.throw())?;
+            let (s, rest) = rest.split_at(len);
             let s = unsafe { String::from_utf8_unchecked(Vec::from(s)) };
-            set.insert(s);
+            if !set.insert(s) {
+                return Err(Error::WrongFileFormat.throw());
+            }
+            bytes = rest;
         }
-        Self { set }
+        // What is left is the padding `to_bytes` adds.
+        if bytes.iter().any(|byte| *byte != 0) {
+            return Err(Error::WrongFileFormat.throw());
+        }
+        Ok(Self { set })
     }
     pub fn position_of(&mut self, s: &str) -> global::Result<u64, GenericError<Error>> {
         match self.set.iter().position(|x| x.eq(s)) {
@@ -94,15 +156,14 @@ impl StringInterner {
         }
         Err(Error::StringNotFound { index: i }.throw())
     }
+    /// The length-prefixed strings, zero-padded to a multiple of 8 bytes.
     pub fn to_bytes(&self) -> Vec<u8> {
         let mut s = self.set.iter().fold(Vec::<u8>::new(), |mut a, b| {
-            let mut s = b.as_bytes().to_vec();
-            s.push(b'\0');
-            a.extend_from_slice(&s);
+            a.extend_from_slice(&(b.len() as u64).to_le_bytes());
+            a.extend_from_slice(b.as_bytes());
             a
         });
-        s.pop();
-        s.extend_from_slice(&vec![0; s.len() % 8]);
+        s.resize(s.len().next_multiple_of(8), 0);
         s
     }
 }
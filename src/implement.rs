@@ -1,14 +1,24 @@
 use crate::method::Method;
 
 use global::derive_ctor::ctor;
-use global::getset::{CopyGetters, Getters};
+use global::getset::{CopyGetters, Getters, MutGetters};
 use global::{IndexMap, StringName, StringTypeReference};
 use proc_macros::{ReadFromFile, WriteToFile};
 
-#[derive(ctor, Debug, Clone, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+/// A block of methods attached to `ty`, either inherent or implementing `interface`.
+///
+/// [`Assembly::implementations`](crate::Assembly::implementations) keys each block by a name
+/// that is unique within the assembly and carries no other meaning; lookups by type or
+/// interface go through `ty` and `interface`.
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
 #[getset(get = "pub")]
+#[ctor(pub new)]
 pub struct Implementation {
-    ty: StringTypeReference,
-    interface: Option<StringTypeReference>,
-    methods: IndexMap<StringName, Method>,
+    pub(crate) ty: StringTypeReference,
+    pub(crate) interface: Option<StringTypeReference>,
+    /// Keyed like the methods of a type definition.
+    #[getset(get_mut = "pub")]
+    pub(crate) methods: IndexMap<StringName, Method>,
 }
@@ -10,14 +10,17 @@
 #![allow(incomplete_features)]
 
 pub mod assembly;
+pub mod builder;
 pub mod core;
-mod implement;
+pub mod implement;
 #[cfg(test)]
 mod tests;
 pub mod traits;
 mod ty;
+pub mod validation;
 
 pub(crate) type Error = global::errors::BinaryError;
 
 pub use assembly::Assembly;
+pub use implement::Implementation;
 pub use ty::*;
@@ -1,8 +1,30 @@
 use crate::assembly::Assembly;
+use crate::builder::{AssemblyBuilder, MethodBuilder};
+use crate::core::{Decoder, Encoder, File, StringInterner};
+use crate::implement::Implementation;
 use crate::method::Method;
+use crate::traits::{ReadFromFile, WriteToFile};
+use crate::ty::assembly_ref::{AssemblyIdentity, AssemblyRef, AssemblyVersion};
+use crate::ty::attribute::{AttributeTarget, CustomAttribute};
 use crate::ty::class::ClassDef;
-use crate::ty::{GenericBinding, TypeDef, class};
-use enumflags2::make_bitflags;
+use crate::ty::constant::ConstantValue;
+use crate::ty::delegate::DelegateDef;
+use crate::ty::r#enum::{EnumDef, EnumMember, EnumUnderlyingType};
+use crate::ty::event::{Event, EventImplementationFlags};
+use crate::ty::exception::{ExceptionClause, HandlerKind, InstructionRange};
+use crate::ty::interface::InterfaceDef;
+use crate::ty::layout::{LayoutKind, StructLayout};
+use crate::ty::local::{LocalFlags, LocalVariable};
+use crate::ty::nesting::{NestedVisibility, Nesting};
+use crate::ty::overrides::{MethodOverride, SlotFlags};
+use crate::ty::parameter::{Parameter, ParameterFlags};
+use crate::ty::property::{Property, PropertyImplementationFlags};
+use crate::ty::registers::instruction_registers;
+use crate::ty::signature::MethodSignature;
+use crate::ty::r#struct::StructDef;
+use crate::ty::{GenericBinding, SpecialConstraint, TypeDef, Variance, class};
+use crate::validation::{ValidationError, ValidationWarning};
+use enumflags2::{BitFlags, make_bitflags};
 use global::StringMethodReference;
 use global::attrs::MethodAttr;
 use global::attrs::MethodImplementationFlags;
@@ -11,12 +33,15 @@ use global::attrs::{
     Visibility,
 };
 use global::instruction::StringInstruction;
-use global::{StringName, StringTypeReference, indexmap, string_name};
+use global::{IndexMap, IndexSet, StringName, StringTypeReference, indexmap, string_name};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
 use std::io::Write;
 use std::sync::Arc;
 
-#[test]
-fn test_emit_get() -> global::Result<()> {
+mod round_trip;
+
+fn test_assembly() -> global::Result<Assembly> {
     const TEST_CLASS_NAME: StringTypeReference =
         StringTypeReference::make_static_single("Test", "Test.Test");
     let mut assem = Assembly::default();
@@ -128,14 +153,39 @@ fn test_emit_get() -> global::Result<()> {
                 ),
             },
             indexmap! {
-                StringName::from_static_str("__test") => class::Field {
-                    name: StringName::from_static_str("__test"),
-                    attr: FieldAttr::new(Visibility::Public, make_bitflags!(FieldImplementationFlags::{Static})),
-                    ty: StringTypeReference::make_static_single("!", "System.String"),
-                }
+                StringName::from_static_str("__test") => class::Field::new(
+                    StringName::from_static_str("__test"),
+                    FieldAttr::new(Visibility::Public, make_bitflags!(FieldImplementationFlags::{Static})),
+                    StringTypeReference::make_static_single("!", "System.String"),
+                )
             }
         )),
     );
+    Ok(assem)
+}
+
+/// The `Test.Test` class of an assembly built from [`test_assembly`].
+fn test_class(assem: &mut Assembly) -> &mut ClassDef {
+    match assem
+        .type_defs_mut()
+        .get_mut(&string_name!("Test.Test"))
+        .unwrap()
+    {
+        TypeDef::Class(class) => class,
+        _ => unreachable!(),
+    }
+}
+
+fn test_struct<'a>(assem: &'a mut Assembly, name: &StringName) -> &'a mut StructDef {
+    match assem.type_defs_mut().get_mut(name).unwrap() {
+        TypeDef::Struct(struct_def) => struct_def,
+        _ => unreachable!(),
+    }
+}
+
+#[test]
+fn test_emit_get() -> global::Result<()> {
+    let assem = test_assembly()?;
     let b = assem.to_file_bytes()?;
     print!("Out to file?[Y/n] ");
     std::io::stdout().flush()?;
@@ -146,7 +196,1506 @@ fn test_emit_get() -> global::Result<()> {
         assert_eq!(std::fs::read("./test.plb")?, b);
     }
     let assem_gotten = Assembly::from_bytes(b)?;
-    dbg!(&assem_gotten);
+    assert_eq!(assem_gotten, assem);
+    Ok(())
+}
+
+#[test]
+fn test_assembly_eq_ignores_map_order() -> global::Result<()> {
+    let assem = test_assembly()?;
+    let TypeDef::Class(class) = &assem.type_defs()[&string_name!("Test.Test")] else {
+        unreachable!()
+    };
+    let mut other_class = class.clone();
+    other_class.name = string_name!("Test.Other");
+    let mut a = assem.clone();
+    a.type_defs_mut().insert(
+        string_name!("Test.Other"),
+        TypeDef::Class(other_class.clone()),
+    );
+    let mut b = Assembly::default();
+    *b.name_mut() = assem.name().clone();
+    b.type_defs_mut()
+        .insert(string_name!("Test.Other"), TypeDef::Class(other_class));
+    b.type_defs_mut().extend(assem.type_defs().clone());
+    assert_eq!(a, b);
+    b.type_defs_mut().remove(&string_name!("Test.Other"));
+    assert_ne!(a, b);
+    Ok(())
+}
+
+#[test]
+fn test_interface_def() -> global::Result<()> {
+    let signature = |name: &'static str, instructions, slot_flags| {
+        let mut method = Method::new(
+            StringName::from_static_str(name),
+            MethodAttr::new(
+                Visibility::Public,
+                make_bitflags!(MethodImplementationFlags::{}),
+                1,
+            ),
+            instructions,
+            StringTypeReference::core_static_single_type("System.Void"),
+            vec![],
+            Default::default(),
+        );
+        *method.slot_flags_mut() = slot_flags;
+        method
+    };
+    let mut assem = Assembly::default();
+    *assem.name_mut() = string_name!("Test");
+    assem.type_defs_mut().insert(
+        string_name!("Test.IGreeter"),
+        TypeDef::Interface(InterfaceDef::new(
+            vec![StringTypeReference::core_static_single_type(
+                "System.IDisposable",
+            )],
+            Default::default(),
+            TypeAttr::new(
+                Visibility::Public,
+                TypeSpecificAttr::Interface(Default::default()),
+            ),
+            string_name!("Test.IGreeter"),
+            indexmap! {
+                string_name!("Greet()") => signature("Greet()", vec![], SlotFlags::Abstract.into()),
+                string_name!("GreetTwice()") => signature("GreetTwice()", vec![
+                    StringInstruction::LoadTrue { register_addr: 0 },
+                ], BitFlags::empty()),
+                string_name!("Reset()") => signature("Reset()", vec![], BitFlags::empty()),
+            },
+        )),
+    );
+    assert_eq!(assem.validate(), Ok(()));
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    let TypeDef::Interface(interface) = &assem_gotten.type_defs()[&string_name!("Test.IGreeter")]
+    else {
+        unreachable!()
+    };
+    assert!(
+        interface
+            .abstract_methods()
+            .map(|(name, _)| name)
+            .eq([&string_name!("Greet()")])
+    );
+    assert!(
+        interface
+            .default_methods()
+            .map(|(name, _)| name)
+            .eq([&string_name!("GreetTwice()"), &string_name!("Reset()")])
+    );
+    let TypeDef::Interface(interface) = assem
+        .type_defs_mut()
+        .get_mut(&string_name!("Test.IGreeter"))
+        .unwrap()
+    else {
+        unreachable!()
+    };
+    interface.methods.insert(
+        string_name!("GreetTwice()"),
+        signature(
+            "GreetTwice()",
+            vec![StringInstruction::LoadTrue { register_addr: 0 }],
+            SlotFlags::Abstract.into(),
+        ),
+    );
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::AbstractMethodWithBody {
+            method: string_name!("GreetTwice()"),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_implementation_validation() -> global::Result<()> {
+    let greeter = StringTypeReference::make_static_single("Test", "Test.IGreeter");
+    let disposable = StringTypeReference::core_static_single_type("System.IDisposable");
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    *class.interfaces_mut() = vec![greeter.clone(), disposable.clone()];
+    let greet = class.methods()[&string_name!("PrintStaticsAndGenericType()")].clone();
+    assem.type_defs_mut().insert(
+        string_name!("Test.IGreeter"),
+        TypeDef::Interface(InterfaceDef::new(
+            vec![],
+            Default::default(),
+            TypeAttr::new(
+                Visibility::Public,
+                TypeSpecificAttr::Interface(Default::default()),
+            ),
+            string_name!("Test.IGreeter"),
+            indexmap! {
+                string_name!("Greet()") => {
+                    let mut signature = Method::new(
+                        string_name!("Greet()"),
+                        greet.attr(),
+                        vec![],
+                        greet.ret_type().clone(),
+                        vec![],
+                        Default::default(),
+                    );
+                    *signature.slot_flags_mut() = make_bitflags!(SlotFlags::{Abstract});
+                    signature
+                },
+            },
+        )),
+    );
+    let key = string_name!("Test.Test@Test.IGreeter");
+    assem.implementations_mut().insert(
+        key.clone(),
+        Implementation::new(
+            StringTypeReference::make_static_single("Test", "Test.Test"),
+            Some(greeter.clone()),
+            indexmap! { string_name!("Greet()") => greet },
+        ),
+    );
+    assem.implementations_mut().insert(
+        string_name!("Test.Test@System.IDisposable"),
+        Implementation::new(
+            StringTypeReference::make_static_single("Test", "Test.Test"),
+            Some(disposable.clone()),
+            indexmap! {},
+        ),
+    );
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+    let mut derived = ClassDef::new(
+        Some(StringTypeReference::make_static_single("Test", "Test.Test")),
+        indexmap! {},
+        TypeAttr::new(
+            Visibility::Public,
+            TypeSpecificAttr::Class(make_bitflags!(ClassImplementationFlags::{})),
+        ),
+        string_name!("Test.Derived"),
+        indexmap! {},
+        indexmap! {},
+    );
+    derived.interfaces_mut().push(greeter.clone());
+    assem
+        .type_defs_mut()
+        .insert(string_name!("Test.Derived"), TypeDef::Class(derived));
+    // The re-declared interface is implemented by the base class.
+    assert_eq!(assem.validate(), Ok(()));
+    let TypeDef::Class(derived) = assem
+        .type_defs_mut()
+        .get_mut(&string_name!("Test.Derived"))
+        .unwrap()
+    else {
+        unreachable!()
+    };
+    let parent = derived.parent.take();
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::MissingInterfaceImplementation {
+            ty: string_name!("Test.Derived"),
+            interface: greeter.clone(),
+        })
+    );
+    let TypeDef::Class(derived) = assem
+        .type_defs_mut()
+        .get_mut(&string_name!("Test.Derived"))
+        .unwrap()
+    else {
+        unreachable!()
+    };
+    derived.parent = parent;
+    derived.interfaces_mut().clear();
+    assert_eq!(assem.validate(), Ok(()));
+    assert!(assem.implements(&string_name!("Test.Test"), &greeter));
+    assert!(assem.implements(&string_name!("Test.Derived"), &disposable));
+    assert!(!assem.implements(&string_name!("Test.IGreeter"), &disposable));
+    assert_eq!(
+        assem.implementations_of(&string_name!("Test.Test")).count(),
+        2
+    );
+    assert_eq!(
+        assem
+            .implementations_of(&string_name!("Test.Derived"))
+            .count(),
+        0
+    );
+    let greet_methods = assem.implementations()[&key].methods().clone();
+    assem.implementations_mut().insert(
+        string_name!("Test.Derived@Test.IGreeter"),
+        Implementation::new(
+            StringTypeReference::make_static_single("Test", "Test.Derived"),
+            Some(greeter.clone()),
+            greet_methods.clone(),
+        ),
+    );
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::UndeclaredInterfaceImplementation {
+            implementation: string_name!("Test.Derived@Test.IGreeter"),
+            interface: greeter.clone(),
+        })
+    );
+    assem.type_defs_mut().remove(&string_name!("Test.Derived"));
+    assem
+        .implementations_mut()
+        .remove(&string_name!("Test.Derived@Test.IGreeter"));
+
+    let implementation = assem.implementations_mut().get_mut(&key).unwrap();
+    implementation.methods_mut().clear();
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::MissingInterfaceMethod {
+            implementation: key.clone(),
+            method: string_name!("Greet()"),
+        })
+    );
+    *assem
+        .implementations_mut()
+        .get_mut(&key)
+        .unwrap()
+        .methods_mut() = greet_methods;
+    let other = string_name!("Other");
+    let test = StringTypeReference::make_static_single("Test", "Test.Test");
+    assem.implementations_mut().insert(
+        other.clone(),
+        Implementation::new(greeter.clone(), Some(test.clone()), indexmap! {}),
+    );
+    assert!(matches!(
+        assem.validate(),
+        Err(ValidationError::NotAnInterface { ty }) if ty == test
+    ));
+    let missing = StringTypeReference::make_static_single("Test", "Test.Missing");
+    assem.implementations_mut().insert(
+        other,
+        Implementation::new(missing.clone(), Some(greeter), indexmap! {}),
+    );
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::UnknownType { ty: missing })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_enum_def_validation() -> global::Result<()> {
+    let color = |underlying, members: &[(&'static str, i128)]| {
+        EnumDef::new(
+            TypeAttr::new(
+                Visibility::Public,
+                TypeSpecificAttr::Struct(Default::default()),
+            ),
+            string_name!("Test.Color"),
+            underlying,
+            members
+                .iter()
+                .map(|&(name, value)| EnumMember::new(StringName::from_static_str(name), value))
+                .collect(),
+            false,
+        )
+    };
+    let valid = color(
+        EnumUnderlyingType::U8,
+        &[("Red", 0), ("Green", 1), ("Blue", 255)],
+    );
+    assert_eq!(valid.validate(), Ok(()));
+    assert_eq!(
+        valid.member(&string_name!("Blue")).map(EnumMember::value),
+        Some(255)
+    );
+
+    let mut assem = Assembly::default();
+    assem
+        .type_defs_mut()
+        .insert(string_name!("Test.Color"), TypeDef::Enum(valid));
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    assert!(matches!(
+        color(EnumUnderlyingType::U8, &[("Red", 256)]).validate(),
+        Err(ValidationError::EnumValueOutOfRange { value: 256, .. })
+    ));
+    assert!(matches!(
+        color(EnumUnderlyingType::I8, &[("Red", -129)]).validate(),
+        Err(ValidationError::EnumValueOutOfRange { value: -129, .. })
+    ));
+    assert!(matches!(
+        color(EnumUnderlyingType::I64, &[("Red", 0), ("Red", 1)]).validate(),
+        Err(ValidationError::DuplicateEnumMember { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_delegate_def() -> global::Result<()> {
+    let mut assem = test_assembly()?;
+    assem.type_defs_mut().insert(
+        string_name!("Test.Callback"),
+        TypeDef::Delegate(DelegateDef::new(
+            indexmap! {
+                string_name!("@T") => GenericBinding::new(vec![], None),
+            },
+            TypeAttr::new(
+                Visibility::Public,
+                TypeSpecificAttr::Class(make_bitflags!(ClassImplementationFlags::{})),
+            ),
+            string_name!("Test.Callback"),
+            vec![StringTypeReference::core_static_single_type(
+                "System.String",
+            )],
+            StringTypeReference::core_static_single_type("System.Void"),
+        )),
+    );
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    assert!(
+        assem_gotten
+            .delegates()
+            .map(|(name, _)| name)
+            .eq([&string_name!("Test.Callback")])
+    );
+    assert!(
+        assem_gotten.type_defs()[&string_name!("Test.Test")]
+            .as_delegate()
+            .is_none()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_property_accessors() -> global::Result<()> {
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    class.properties_mut().insert(
+        string_name!("Test"),
+        Property::new(
+            string_name!("Test"),
+            StringTypeReference::core_static_single_type("System.String"),
+            Some(StringMethodReference::Single(string_name!(
+                "PrintStaticsAndGenericType()"
+            ))),
+            None,
+            make_bitflags!(PropertyImplementationFlags::{Static}),
+        ),
+    );
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    let class = test_class(&mut assem);
+    class.properties_mut()[&string_name!("Test")].setter =
+        Some(StringMethodReference::Single(string_name!("set_Test()")));
+    assert!(matches!(
+        assem.validate(),
+        Err(ValidationError::MissingAccessor { accessor, .. })
+            if accessor == StringMethodReference::Single(string_name!("set_Test()"))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_event_accessors() -> global::Result<()> {
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    let main =
+        StringMethodReference::Single(string_name!("Main([!]System.Array`1[@T:[!]System.String])"));
+    class.events_mut().insert(
+        string_name!("Printed"),
+        Event::new(
+            string_name!("Printed"),
+            StringTypeReference::make_static_single("Test", "Test.Callback"),
+            main.clone(),
+            main,
+            None,
+            make_bitflags!(EventImplementationFlags::{Static}),
+        ),
+    );
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    let class = test_class(&mut assem);
+    class.events_mut()[&string_name!("Printed")].raise = Some(StringMethodReference::Single(
+        string_name!("raise_Printed()"),
+    ));
+    assert!(matches!(
+        assem.validate(),
+        Err(ValidationError::MissingAccessor { member, .. }) if member == string_name!("Printed")
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_field_constant() -> global::Result<()> {
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    let mut answer = class::Field::new(
+        string_name!("Answer"),
+        FieldAttr::new(
+            Visibility::Public,
+            make_bitflags!(FieldImplementationFlags::{Static}),
+        ),
+        StringTypeReference::core_static_single_type("System.Int32"),
+    );
+    *answer.constant_mut() = Some(ConstantValue::I32(42));
+    class.fields.insert(string_name!("Answer"), answer);
+    *class.fields[&string_name!("__test")].constant_mut() =
+        Some(ConstantValue::String(string_name!("10")));
+
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    let test = string_name!("Test.Test");
+    assert_eq!(
+        assem_gotten.field_constant(&test, &string_name!("Answer")),
+        Some(&ConstantValue::I32(42))
+    );
+    assert_eq!(
+        assem_gotten.field_constant(&test, &string_name!("__test")),
+        Some(&ConstantValue::String(string_name!("10")))
+    );
+    assert_eq!(
+        assem_gotten.field_constant(&test, &string_name!("Missing")),
+        None
+    );
+    assert_eq!(ConstantValue::F64(f64::NAN), ConstantValue::F64(f64::NAN));
+    assert_ne!(ConstantValue::F64(0.0), ConstantValue::F64(-0.0));
+    Ok(())
+}
+
+#[test]
+fn test_custom_attributes() -> global::Result<()> {
+    let obsolete_type = StringTypeReference::core_static_single_type("System.ObsoleteAttribute");
+    let obsolete = CustomAttribute::new(
+        obsolete_type.clone(),
+        StringMethodReference::Single(string_name!(".ctor([!]System.String)")),
+        vec![ConstantValue::String(string_name!("use Main2"))],
+        indexmap! { string_name!("IsError") => ConstantValue::Bool(true) },
+    );
+    let serializable = CustomAttribute::new(
+        StringTypeReference::core_static_single_type("System.SerializableAttribute"),
+        StringMethodReference::Single(string_name!(".ctor()")),
+        vec![],
+        indexmap! {},
+    );
+    let main = string_name!("Main([!]System.Array`1[@T:[!]System.String])");
+
+    let mut assem = test_assembly()?;
+    assem.custom_attributes_mut().push(obsolete.clone());
+    let class = test_class(&mut assem);
+    class.custom_attributes_mut().push(serializable);
+    let method = class.methods.get_mut(&main).unwrap();
+    method.custom_attributes_mut().push(obsolete.clone());
+    method.params_mut()[0]
+        .custom_attributes_mut()
+        .push(obsolete.clone());
+    class.fields[&string_name!("__test")]
+        .custom_attributes_mut()
+        .push(obsolete.clone());
+    let string = StringTypeReference::core_static_single_type("System.String");
+    let mut property = Property::new(
+        string_name!("Text"),
+        string.clone(),
+        None,
+        None,
+        BitFlags::empty(),
+    );
+    property.custom_attributes_mut().push(obsolete.clone());
+    class.properties.insert(string_name!("Text"), property);
+    let handler = StringMethodReference::Single(main.clone());
+    let mut event = Event::new(
+        string_name!("Changed"),
+        string,
+        handler.clone(),
+        handler,
+        None,
+        BitFlags::empty(),
+    );
+    event.custom_attributes_mut().push(obsolete);
+    class.events.insert(string_name!("Changed"), event);
+    let method = class.methods[&main].clone();
+    assem.implementations_mut().insert(
+        string_name!("Impl"),
+        Implementation::new(
+            StringTypeReference::make_static_single("Test", "Test.Test"),
+            None,
+            indexmap! { main.clone() => method },
+        ),
+    );
+
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    let ty = string_name!("Test.Test");
+    let mut found = assem_gotten.items_with_attribute(&obsolete_type);
+    found.sort_by_key(|target| format!("{target:?}"));
+    assert_eq!(
+        found,
+        [
+            AttributeTarget::Assembly,
+            AttributeTarget::Event {
+                ty: &ty,
+                event: &string_name!("Changed"),
+            },
+            AttributeTarget::Field {
+                ty: &ty,
+                field: &string_name!("__test"),
+            },
+            AttributeTarget::ImplementationMethod {
+                implementation: &string_name!("Impl"),
+                method: &main,
+            },
+            AttributeTarget::ImplementationParameter {
+                implementation: &string_name!("Impl"),
+                method: &main,
+                index: 0,
+            },
+            AttributeTarget::Method {
+                ty: &ty,
+                method: &main,
+            },
+            AttributeTarget::Parameter {
+                ty: &ty,
+                method: &main,
+                index: 0,
+            },
+            AttributeTarget::Property {
+                ty: &ty,
+                property: &string_name!("Text"),
+            },
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_method_params() -> global::Result<()> {
+    let string = StringTypeReference::core_static_single_type("System.String");
+    let int = StringTypeReference::core_static_single_type("System.Int32");
+    let method = Method::with_params(
+        string_name!("TryParse([!]System.String,[!]System.Int32)"),
+        MethodAttr::new(
+            Visibility::Public,
+            make_bitflags!(MethodImplementationFlags::{Static}),
+            0,
+        ),
+        vec![],
+        StringTypeReference::core_static_single_type("System.Boolean"),
+        vec![
+            Parameter::new(
+                string_name!("text"),
+                string.clone(),
+                BitFlags::empty(),
+                None,
+            ),
+            Parameter::new(
+                string_name!("result"),
+                int.clone(),
+                make_bitflags!(ParameterFlags::{Out}),
+                Some(ConstantValue::I32(0)),
+            ),
+        ],
+        indexmap! {},
+    );
+    assert!(method.args().eq([&string, &int]));
+    assert_eq!(method.params()[1].name(), &string_name!("result"));
+    assert!(method.params()[1].flags().contains(ParameterFlags::Out));
+    assert_eq!(round_trip(&method)?, method);
+
+    let legacy = Method::new(
+        method.name().clone(),
+        method.attr(),
+        vec![],
+        method.ret_type().clone(),
+        vec![string.clone(), int.clone()],
+        indexmap! {},
+    );
+    assert!(legacy.args().eq(method.args()));
+    assert_eq!(legacy.params()[0], Parameter::unnamed(string));
+    Ok(())
+}
+
+#[test]
+fn test_method_locals() -> global::Result<()> {
+    let main = string_name!("Main([!]System.Array`1[@T:[!]System.String])");
+    let int = StringTypeReference::core_static_single_type("System.Int64");
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    let method = class.methods.get_mut(&main).unwrap();
+    *method.locals_mut() = vec![
+        LocalVariable::new(
+            StringTypeReference::core_static_single_type("System.Object"),
+            None,
+            make_bitflags!(LocalFlags::{Pinned}),
+        ),
+        LocalVariable::new(int.clone(), Some(string_name!("code")), BitFlags::empty()),
+    ];
+    assert_eq!(assem.validate(), Ok(()));
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    let TypeDef::Class(class) = &assem_gotten.type_defs()[&string_name!("Test.Test")] else {
+        unreachable!()
+    };
+    let local = class.methods()[&main].local(1).unwrap();
+    assert_eq!(local.ty(), &int);
+    assert_eq!(local.name(), &Some(string_name!("code")));
+    assert_eq!(class.methods()[&main].local(2), None);
+
+    let class = test_class(&mut assem);
+    let locals = class.methods.get_mut(&main).unwrap().locals_mut();
+    locals.resize(11, locals[1].clone());
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::TooManyLocals {
+            method: main,
+            locals: 11,
+            register_len: 10,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_required_registers() {
+    let call = StringInstruction::InstanceCall {
+        val: 5,
+        method: StringMethodReference::Single(string_name!("ToString()")),
+        args: vec![2],
+        ret_at: 7,
+    };
+    assert_eq!(instruction_registers(&call), vec![5, 2, 7]);
+    assert_eq!(
+        instruction_registers(&StringInstruction::LoadArg {
+            register_addr: 1,
+            arg: 9,
+        }),
+        vec![1]
+    );
+    let method = |register_len| {
+        Method::new(
+            string_name!("Run()"),
+            MethodAttr::new(Visibility::Public, BitFlags::empty(), register_len),
+            vec![
+                call.clone(),
+                StringInstruction::ReturnVal { register_addr: 7 },
+            ],
+            StringTypeReference::core_static_single_type("System.Void"),
+            vec![],
+            IndexMap::new(),
+        )
+    };
+    assert_eq!(method(8).required_registers(), Some(8));
+    assert_eq!(method(8).validate(), Ok(()));
+    assert_eq!(method(8).register_warning(), None);
+    assert_eq!(
+        method(10).register_warning(),
+        Some(ValidationWarning::ExcessRegisters {
+            method: string_name!("Run()"),
+            register_len: 10,
+            required: 8,
+        })
+    );
+    assert_eq!(
+        method(7).validate(),
+        Err(ValidationError::TooFewRegisters {
+            method: string_name!("Run()"),
+            register_len: 7,
+            required: 8,
+        })
+    );
+    let last = Method::new(
+        string_name!("Run()"),
+        MethodAttr::new(Visibility::Public, BitFlags::empty(), u64::MAX),
+        vec![StringInstruction::ReturnVal {
+            register_addr: u64::MAX,
+        }],
+        StringTypeReference::core_static_single_type("System.Void"),
+        vec![],
+        IndexMap::new(),
+    );
+    assert_eq!(last.required_registers(), None);
+    let last_register = |m: MethodBuilder| {
+        m.instruction(StringInstruction::ReturnVal {
+            register_addr: u64::MAX,
+        })
+    };
+    let builder = AssemblyBuilder::new("Test").class("Test.Test");
+    assert!(
+        builder
+            .method("Run", last_register)
+            .finish()
+            .build()
+            .is_err()
+    );
+    assert_eq!(last.register_warning(), None);
+    assert_eq!(
+        last.validate(),
+        Err(ValidationError::RegisterOutOfRange {
+            method: string_name!("Run()"),
+        })
+    );
+}
+
+#[test]
+fn test_exception_clauses() -> global::Result<()> {
+    let range = InstructionRange::new;
+    let exception = StringTypeReference::core_static_single_type("System.Exception");
+    let mut method = Method::new(
+        string_name!("Run()"),
+        MethodAttr::new(
+            Visibility::Public,
+            make_bitflags!(MethodImplementationFlags::{Static}),
+            1,
+        ),
+        vec![
+            StringInstruction::Load_u64 {
+                register_addr: 0,
+                val: 0,
+            };
+            6
+        ],
+        StringTypeReference::core_static_single_type("System.Void"),
+        vec![],
+        indexmap! {},
+    );
+    *method.exception_clauses_mut() = vec![
+        ExceptionClause::new(range(0, 2), range(2, 3), HandlerKind::Catch(exception)),
+        ExceptionClause::new(range(0, 2), range(4, 5), HandlerKind::Filter(range(3, 4))),
+        ExceptionClause::new(range(0, 5), range(5, 6), HandlerKind::Finally),
+    ];
+    assert_eq!(method.validate(), Ok(()));
+    assert_eq!(round_trip(&method)?, method);
+
+    method.exception_clauses_mut()[2].handler_range = range(5, 7);
+    assert_eq!(
+        method.validate(),
+        Err(ValidationError::ExceptionRangeOutOfBounds {
+            method: string_name!("Run()"),
+            range: range(5, 7),
+            len: 6,
+        })
+    );
+    method.exception_clauses_mut()[2] =
+        ExceptionClause::new(range(1, 4), range(5, 6), HandlerKind::Fault);
+    assert_eq!(
+        method.validate(),
+        Err(ValidationError::ExceptionRangesOverlap {
+            method: string_name!("Run()"),
+            first: range(0, 2),
+            second: range(1, 4),
+        })
+    );
+    method.exception_clauses_mut()[2] =
+        ExceptionClause::new(range(0, 5), range(4, 6), HandlerKind::Fault);
+    assert_eq!(
+        method.validate(),
+        Err(ValidationError::ExceptionRangesOverlap {
+            method: string_name!("Run()"),
+            first: range(0, 5),
+            second: range(4, 6),
+        })
+    );
+    method.exception_clauses_mut()[2] =
+        ExceptionClause::new(range(0, 5), range(5, 6), HandlerKind::Finally);
+    method.exception_clauses_mut().rotate_right(1);
+    assert_eq!(
+        method.validate(),
+        Err(ValidationError::ExceptionClausesOutOfOrder {
+            method: string_name!("Run()"),
+            outer: range(0, 5),
+            inner: range(0, 2),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_nested_types() -> global::Result<()> {
+    let outer = string_name!("Test.Test");
+    let inner = string_name!("Test.Test.Inner");
+    let deepest = string_name!("Test.Test.Inner.Deepest");
+    let color = string_name!("Test.Color");
+    let nested_class = |name: &StringName, enclosing: &StringName, visibility| {
+        let mut class_def = ClassDef::new(
+            None,
+            indexmap! {},
+            TypeAttr::new(
+                Visibility::Public,
+                TypeSpecificAttr::Class(make_bitflags!(ClassImplementationFlags::{})),
+            ),
+            name.clone(),
+            indexmap! {},
+            indexmap! {},
+        );
+        *class_def.nesting_mut() = Some(Nesting::new(enclosing.clone(), visibility));
+        TypeDef::Class(class_def)
+    };
+
+    let mut assem = test_assembly()?;
+    assem.type_defs_mut().insert(
+        color.clone(),
+        TypeDef::Enum(EnumDef::new(
+            TypeAttr::new(
+                Visibility::Public,
+                TypeSpecificAttr::Struct(Default::default()),
+            ),
+            color.clone(),
+            EnumUnderlyingType::U8,
+            vec![],
+            false,
+        )),
+    );
+    assem.type_defs_mut().insert(
+        inner.clone(),
+        nested_class(&inner, &outer, NestedVisibility::Private),
+    );
+    assem.type_defs_mut().insert(
+        deepest.clone(),
+        nested_class(&deepest, &inner, NestedVisibility::Public),
+    );
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    assert_eq!(assem.enclosing_type(&deepest), Some(&inner));
+    assert_eq!(assem.enclosing_type(&outer), None);
+    let nested = assem
+        .nested_types(&outer)
+        .map(|(name, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(nested, [&inner]);
+    assert!(assem.is_within(&deepest, &outer));
+    assert!(!assem.is_within(&outer, &inner));
+    assert!(assem.is_accessible_from(&deepest, &outer));
+    assert!(assem.is_accessible_from(&inner, &deepest));
+    assert!(!assem.is_accessible_from(&deepest, &color));
+    assert!(assem.is_accessible_from(&outer, &color));
+
+    *assem
+        .type_defs_mut()
+        .get_mut(&deepest)
+        .unwrap()
+        .nesting_mut() = Some(Nesting::new(color.clone(), NestedVisibility::Public));
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::EnclosingTypeCannotNest {
+            ty: deepest.clone(),
+            enclosing: color.clone(),
+        })
+    );
+    *assem
+        .type_defs_mut()
+        .get_mut(&deepest)
+        .unwrap()
+        .nesting_mut() = Some(Nesting::new(
+        string_name!("Test.Missing"),
+        NestedVisibility::Public,
+    ));
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::UnknownEnclosingType {
+            ty: deepest.clone(),
+            enclosing: string_name!("Test.Missing"),
+        })
+    );
+    assem.type_defs_mut().remove(&deepest);
+    *assem.type_defs_mut().get_mut(&outer).unwrap().nesting_mut() =
+        Some(Nesting::new(inner.clone(), NestedVisibility::Private));
+    assert!(matches!(
+        assem.validate(),
+        Err(ValidationError::NestingCycle { ty }) if ty == outer || ty == inner
+    ));
+    assert!(assem.is_within(&outer, &inner));
+    assert!(!assem.is_within(&outer, &color));
+    assert!(!assem.is_accessible_from(&outer, &color));
+    assert!(assem.is_accessible_from(&outer, &inner));
+    Ok(())
+}
+
+#[test]
+fn test_generic_constraints() -> global::Result<()> {
+    let t = string_name!("@T");
+    let u = string_name!("@U");
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    let binding = &mut class.type_vars[&u];
+    *binding.special_constraints_mut() =
+        make_bitflags!(SpecialConstraint::{ValueType | DefaultConstructor});
+    binding.type_var_constraints_mut().push(t.clone());
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    let class = test_class(&mut assem);
+    *class.type_vars[&u].special_constraints_mut() |= SpecialConstraint::ReferenceType;
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::ConflictingGenericConstraints { var: u.clone() })
+    );
+    let class = test_class(&mut assem);
+    *class.type_vars[&u].special_constraints_mut() = BitFlags::empty();
+    class.type_vars[&t]
+        .type_var_constraints_mut()
+        .push(u.clone());
+    assert!(matches!(
+        assem.validate(),
+        Err(ValidationError::TypeVarConstraintCycle { var }) if var == t || var == u
+    ));
+
+    let mut binding = GenericBinding::new(vec![], None);
+    *binding.variance_mut() = Variance::Covariant;
+    assert_eq!(binding.validate(&t, |_| true, true), Ok(()));
+    assert_eq!(
+        binding.validate(&t, |_| true, false),
+        Err(ValidationError::VarianceNotAllowed { var: t.clone() })
+    );
+    binding.type_var_constraints_mut().push(u.clone());
+    assert_eq!(
+        binding.validate(&t, |var| var == &t, true),
+        Err(ValidationError::InvalidTypeVarConstraint {
+            var: t.clone(),
+            constraint: u,
+        })
+    );
+
+    let mut assem = AssemblyBuilder::new("Test")
+        .interface("Test.IBox")
+        .type_var("@T")
+        .method("Get", |m| m.type_var("@U"))
+        .finish()
+        .build()?;
+    let TypeDef::Interface(interface) = assem
+        .type_defs_mut()
+        .get_mut(&string_name!("Test.IBox"))
+        .unwrap()
+    else {
+        unreachable!()
+    };
+    *interface.type_vars[&t].variance_mut() = Variance::Covariant;
+    assert_eq!(assem.validate(), Ok(()));
+    let TypeDef::Interface(interface) = assem
+        .type_defs_mut()
+        .get_mut(&string_name!("Test.IBox"))
+        .unwrap()
+    else {
+        unreachable!()
+    };
+    let method = interface.methods.get_mut(&string_name!("Get`1()")).unwrap();
+    *method.type_vars_mut()[&string_name!("@U")].variance_mut() = Variance::Covariant;
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::VarianceNotAllowed {
+            var: string_name!("@U")
+        })
+    );
+
+    let mut binding = GenericBinding::new(vec![], None);
+    binding
+        .type_var_constraints_mut()
+        .push(string_name!("@Missing"));
+    let delegate = DelegateDef::new(
+        indexmap! { t.clone() => binding },
+        TypeAttr::new(
+            Visibility::Public,
+            TypeSpecificAttr::Class(make_bitflags!(ClassImplementationFlags::{})),
+        ),
+        string_name!("Test.Callback"),
+        vec![],
+        StringTypeReference::core_static_single_type("System.Void"),
+    );
+    let mut assem = Assembly::default();
+    assem
+        .type_defs_mut()
+        .insert(string_name!("Test.Callback"), TypeDef::Delegate(delegate));
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::InvalidTypeVarConstraint {
+            var: t,
+            constraint: string_name!("@Missing"),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_explicit_struct_layout() -> global::Result<()> {
+    let union = string_name!("Test.Union");
+    let field = |name: &'static str, ty, impl_flags, offset| {
+        let mut field = class::Field::new(
+            StringName::from_static_str(name),
+            FieldAttr::new(Visibility::Public, impl_flags),
+            StringTypeReference::core_static_single_type(ty),
+        );
+        *field.offset_mut() = offset;
+        (StringName::from_static_str(name), field)
+    };
+    let mut struct_def = StructDef::new(
+        None,
+        indexmap! {},
+        TypeAttr::new(
+            Visibility::Public,
+            TypeSpecificAttr::Struct(Default::default()),
+        ),
+        union.clone(),
+        indexmap! {},
+        IndexMap::from_iter([
+            field("Int", "System.Int32", BitFlags::empty(), Some(0)),
+            field("Float", "System.Single", BitFlags::empty(), Some(0)),
+            field("Object", "System.Object", BitFlags::empty(), Some(8)),
+            field(
+                "Shared",
+                "System.Object",
+                make_bitflags!(FieldImplementationFlags::{Static}),
+                None,
+            ),
+        ]),
+    );
+    *struct_def.layout_mut() = StructLayout::new(LayoutKind::Explicit, Some(4), Some(16));
+    let mut assem = test_assembly()?;
+    assem
+        .type_defs_mut()
+        .insert(union.clone(), TypeDef::Struct(struct_def));
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    let struct_def = test_struct(&mut assem, &union);
+    *struct_def.fields[&string_name!("Object")].offset_mut() = Some(2);
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::OverlappingReferenceField {
+            ty: union.clone(),
+            field: string_name!("Object"),
+            other: string_name!("Int"),
+        })
+    );
+    let struct_def = test_struct(&mut assem, &union);
+    *struct_def.fields[&string_name!("Object")].offset_mut() = Some(12);
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::FieldExceedsStructSize {
+            ty: union.clone(),
+            field: string_name!("Object"),
+            end: 20,
+            size: 16,
+        })
+    );
+    *test_struct(&mut assem, &union).fields[&string_name!("Object")].offset_mut() = Some(8);
+
+    let mut holder = StructDef::new(
+        None,
+        indexmap! {},
+        TypeAttr::new(
+            Visibility::Public,
+            TypeSpecificAttr::Struct(Default::default()),
+        ),
+        string_name!("Test.Holder"),
+        indexmap! {},
+        IndexMap::from_iter([
+            field("Flag", "System.Boolean", BitFlags::empty(), None),
+            field("Object", "System.Object", BitFlags::empty(), None),
+        ]),
+    );
+    *holder.layout_mut() = StructLayout::new(LayoutKind::Sequential, None, None);
+    assem
+        .type_defs_mut()
+        .insert(string_name!("Test.Holder"), TypeDef::Struct(holder));
+    let layout_warnings = |assem: &Assembly| {
+        assem
+            .warnings()
+            .into_iter()
+            .filter(|warning| matches!(warning, ValidationWarning::UnknownFieldLayout { .. }))
+            .collect::<Vec<_>>()
+    };
+    let unknown_layout = vec![ValidationWarning::UnknownFieldLayout {
+        ty: union.clone(),
+        field: string_name!("Inner"),
+    }];
+    for (ty, expected, warnings) in [
+        (
+            "[Test]Test.Holder",
+            Err(ValidationError::OverlappingReferenceField {
+                ty: union.clone(),
+                field: string_name!("Inner"),
+                other: string_name!("Int"),
+            }),
+            vec![],
+        ),
+        ("[Test]Test.Union", Ok(()), unknown_layout.clone()),
+        ("[Other]Other.Value", Ok(()), unknown_layout.clone()),
+        ("[!]System.Decimal", Ok(()), unknown_layout.clone()),
+    ] {
+        let mut inner = field("Inner", "System.Int32", BitFlags::empty(), Some(0)).1;
+        inner.ty = StringTypeReference::from_string_repr(ty)?;
+        test_struct(&mut assem, &union)
+            .fields
+            .insert(string_name!("Inner"), inner);
+        assert_eq!(assem.validate(), expected, "{ty}");
+        assert_eq!(layout_warnings(&assem), warnings, "{ty}");
+    }
+    // The holder is 16 bytes: the flag is padded to the alignment of the reference.
+    let struct_def = test_struct(&mut assem, &union);
+    struct_def.fields[&string_name!("Inner")].ty =
+        StringTypeReference::from_string_repr("[Test]Test.Holder")?;
+    *struct_def.fields[&string_name!("Inner")].offset_mut() = Some(4);
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::FieldExceedsStructSize {
+            ty: union.clone(),
+            field: string_name!("Inner"),
+            end: 20,
+            size: 16,
+        })
+    );
+    test_struct(&mut assem, &union)
+        .fields
+        .shift_remove(&string_name!("Inner"));
+    let struct_def = test_struct(&mut assem, &union);
+    *struct_def.fields[&string_name!("Float")].offset_mut() = None;
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::MissingFieldOffset {
+            ty: union.clone(),
+            field: string_name!("Float"),
+        })
+    );
+    let struct_def = test_struct(&mut assem, &union);
+    *struct_def.layout_mut() = StructLayout::default();
+    assert_eq!(
+        assem.validate(),
+        Err(ValidationError::UnexpectedFieldOffset {
+            ty: union,
+            field: string_name!("Int"),
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_method_overrides() -> global::Result<()> {
+    let object = StringTypeReference::from_string_repr("[!]System.Object")?;
+    let to_string = StringMethodReference::Single(string_name!("ToString()"));
+    let print = string_name!("PrintStaticsAndGenericType()");
+    let mut assem = test_assembly()?;
+    let class = test_class(&mut assem);
+    class.overrides_mut().push(MethodOverride::new(
+        object.clone(),
+        to_string.clone(),
+        StringMethodReference::Single(print.clone()),
+    ));
+    assert_eq!(
+        class.validate(),
+        Err(ValidationError::NonVirtualOverride {
+            ty: string_name!("Test.Test"),
+            method: print.clone(),
+        })
+    );
+    *class.methods[&print].slot_flags_mut() = make_bitflags!(SlotFlags::{Virtual | Final});
+    assert_eq!(assem.validate(), Ok(()));
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    let TypeDef::Class(class) = &assem_gotten.type_defs()[&string_name!("Test.Test")] else {
+        unreachable!()
+    };
+    assert_eq!(
+        class
+            .overriding_method(&object, &to_string)
+            .map(Method::name),
+        Some(&print)
+    );
+    assert_eq!(
+        class.overriding_method(
+            &object,
+            &StringMethodReference::Single(string_name!("Equals()"))
+        ),
+        None
+    );
+
+    let class = test_class(&mut assem);
+    let duplicate = class.overrides()[0].clone();
+    class.overrides_mut().push(duplicate);
+    assert_eq!(
+        class.validate(),
+        Err(ValidationError::DuplicateOverride {
+            ty: string_name!("Test.Test"),
+            declaration: to_string.clone(),
+        })
+    );
+    let missing = StringMethodReference::Single(string_name!("Missing()"));
+    class.overrides_mut()[1] = MethodOverride::new(object, to_string, missing.clone());
+    assert_eq!(
+        class.validate(),
+        Err(ValidationError::UnknownOverrideImplementation {
+            ty: string_name!("Test.Test"),
+            method: missing,
+        })
+    );
+    class.overrides_mut().clear();
+    *class.methods[&print].slot_flags_mut() = make_bitflags!(SlotFlags::{NewSlot});
+    assert_eq!(
+        class.validate(),
+        Err(ValidationError::SlotFlagsWithoutVirtual { method: print })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_method_signature() -> global::Result<()> {
+    let main = string_name!("Main([!]System.Array`1[@T:[!]System.String])");
+    let print = string_name!("PrintStaticsAndGenericType()");
+    let assem = test_assembly()?;
+    let TypeDef::Class(class) = &assem.type_defs()[&string_name!("Test.Test")] else {
+        unreachable!()
+    };
+    assert_eq!(MethodSignature::of(&class.methods()[&main]).key(), main);
+    assert_eq!(MethodSignature::of(&class.methods()[&print]).key(), print);
+    assert_eq!(
+        MethodSignature::of(&class.methods()[&main]).name(),
+        &string_name!("Main")
+    );
+
+    let mut class = ClassDef::new(
+        None,
+        indexmap! {},
+        class.attr(),
+        string_name!("Test.Converter"),
+        indexmap! {},
+        indexmap! {},
+    );
+    let convert = Method::new(
+        string_name!("Convert"),
+        MethodAttr::new(
+            Visibility::Public,
+            make_bitflags!(MethodImplementationFlags::{Static}),
+            1,
+        ),
+        vec![],
+        StringTypeReference::core_static_single_type("System.Object"),
+        vec![StringTypeReference::core_static_single_type(
+            "System.String",
+        )],
+        indexmap! { string_name!("@T") => GenericBinding::new(vec![], None) },
+    );
+    assert_eq!(class.insert_method(convert.clone()), None);
+    assert_eq!(class.insert_method(convert.clone()), Some(convert.clone()));
+    let key = string_name!("Convert`1([!]System.String)");
+    assert!(class.methods().keys().eq([&key]));
+    assert_eq!(class.validate_method_keys(), Ok(()));
+
+    let mut by_ref = convert.clone();
+    by_ref.params_mut()[0].flags = ParameterFlags::Ref.into();
+    assert_eq!(class.insert_method(by_ref.clone()), None);
+    let mut by_out = convert.clone();
+    by_out.params_mut()[0].flags = ParameterFlags::Out | ParameterFlags::Params;
+    assert_eq!(class.insert_method(by_out), None);
+    assert!(class.methods().keys().eq([
+        &key,
+        &string_name!("Convert`1(ref [!]System.String)"),
+        &string_name!("Convert`1(out [!]System.String)"),
+    ]));
+    assert_eq!(class.validate_method_keys(), Ok(()));
+    class.methods.clear();
+
+    class.methods.insert(string_name!("Convert"), convert);
+    assert_eq!(
+        class.validate_method_keys(),
+        Err(ValidationError::MethodKeyMismatch {
+            ty: string_name!("Test.Converter"),
+            key: string_name!("Convert"),
+            expected: key,
+        })
+    );
+    Ok(())
+}
+
+#[test]
+fn test_assembly_builder() -> global::Result<()> {
+    let main = string_name!("Main([!]System.Array`1[@T:[!]System.String])");
+    let print = string_name!("PrintStaticsAndGenericType()");
+    let assem = AssemblyBuilder::new("Test")
+        .class("Test.Test")
+        .extends("[!]System.Object")
+        .implements("[Test]Test.IGreeter", |i| {
+            i.method("Greet", |m| {
+                m.instruction(StringInstruction::LoadTrue { register_addr: 3 })
+            })
+        })
+        .static_field("__test", "[!]System.String")
+        .static_method("PrintStaticsAndGenericType", |m| m)
+        .static_method("Main", |m| {
+            m.param("args", "[!]System.Array`1[@T:[!]System.String]")
+                .instructions([
+                    StringInstruction::StaticCall {
+                        ty: StringTypeReference::make_static_single("Test", "Test.Test"),
+                        method: StringMethodReference::Single(print.clone()),
+                        args: vec![],
+                        ret_at: 1,
+                    },
+                    StringInstruction::Load_u64 {
+                        register_addr: 1,
+                        val: 0,
+                    },
+                    StringInstruction::ReturnVal { register_addr: 1 },
+                ])
+        })
+        .method("Greet", |m| m.registers(4))
+        .finish()
+        .interface("Test.IGreeter")
+        .method("Greet", |m| m)
+        .finish()
+        .build()?;
+    assert_eq!(assem.name(), &string_name!("Test"));
+    let implementation = &assem.implementations()[&string_name!("Test.Test:[Test]Test.IGreeter")];
+    assert_eq!(
+        implementation.methods()[&string_name!("Greet()")]
+            .attr()
+            .register_len(),
+        4
+    );
+    assert_eq!(assem.validate(), Ok(()));
+    assert_eq!(assem.validate_method_keys(), Ok(()));
+    assert_eq!(
+        assem.warnings(),
+        vec![ValidationWarning::ExcessRegisters {
+            method: string_name!("Greet()"),
+            register_len: 4,
+            required: 0,
+        }]
+    );
+    assert_eq!(Assembly::from_bytes(assem.to_file_bytes()?)?, assem);
+
+    let TypeDef::Class(class) = &assem.type_defs()[&string_name!("Test.Test")] else {
+        unreachable!()
+    };
+    assert_eq!(
+        class.parent(),
+        &Some(StringTypeReference::from_string_repr("[!]System.Object")?)
+    );
+    assert!(
+        class
+            .methods()
+            .keys()
+            .eq([&print, &main, &string_name!("Greet()")])
+    );
+    let main_method = &class.methods()[&main];
+    assert_eq!(main_method.name(), &main);
+    assert_eq!(main_method.attr().register_len(), 2);
+    assert!(
+        main_method
+            .attr()
+            .impl_flags()
+            .contains(MethodImplementationFlags::Static)
+    );
+    assert_eq!(main_method.params()[0].name(), &string_name!("args"));
+    assert_eq!(
+        main_method.ret_type(),
+        &StringTypeReference::core_static_single_type("System.Void")
+    );
+    assert_eq!(class.methods()[&print].attr().register_len(), 0);
+    assert_eq!(
+        class.methods()[&string_name!("Greet()")]
+            .attr()
+            .register_len(),
+        4
+    );
+    assert!(
+        class.fields()[&string_name!("__test")]
+            .attr()
+            .impl_flags()
+            .contains(FieldImplementationFlags::Static)
+    );
+
+    assert!(
+        AssemblyBuilder::new("Test")
+            .class("Test.Test")
+            .extends("System.Object")
+            .finish()
+            .build()
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_encoded_size() -> global::Result<()> {
+    let assem = test_assembly()?;
+    let mut file = File::default();
+    assem.write_to_file(&mut file.encoder())?;
+    assert_eq!(
+        assem.encoded_size(file.interner()),
+        file.data.get_ref().len()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_encode_without_file() -> global::Result<()> {
+    let assem = test_assembly()?;
+    let TypeDef::Class(class) = &assem.type_defs()[&string_name!("Test.Test")] else {
+        unreachable!()
+    };
+    let method = &class.methods()[&string_name!("Main([!]System.Array`1[@T:[!]System.String])")];
+    let mut interner = StringInterner::default();
+    let mut bytes = Vec::new();
+    method.write_to_file(&mut Encoder::new(&mut interner, &mut bytes))?;
+    assert_eq!(method.encoded_size(&interner), bytes.len());
+
+    let mut reader = bytes.as_slice();
+    let method_gotten = Method::read_from_file(&mut Decoder::new(&interner, &mut reader))?;
+    assert!(reader.is_empty());
+    let mut bytes_again = Vec::new();
+    method_gotten.write_to_file(&mut Encoder::new(&mut interner, &mut bytes_again))?;
+    assert_eq!(bytes, bytes_again);
+    Ok(())
+}
+
+fn round_trip<T: ReadFromFile + WriteToFile>(val: &T) -> global::Result<T> {
+    let mut interner = StringInterner::default();
+    let mut bytes = Vec::new();
+    val.write_to_file(&mut Encoder::new(&mut interner, &mut bytes))?;
+    assert_eq!(val.encoded_size(&interner), bytes.len());
+    let mut reader = bytes.as_slice();
+    let val_gotten = T::read_from_file(&mut Decoder::new(&interner, &mut reader))?;
+    assert!(reader.is_empty());
+    Ok(val_gotten)
+}
+
+#[test]
+fn test_std_impls() -> global::Result<()> {
+    assert!(round_trip(&true)?);
+    assert!(!round_trip(&false)?);
+    assert_eq!(round_trip(&1.5f32)?, 1.5);
+    assert_eq!(round_trip(&-2.25f64)?, -2.25);
+    assert_eq!(round_trip(&'ä')?, 'ä');
+    assert_eq!(round_trip(&usize::MAX)?, usize::MAX);
+    assert_eq!(*round_trip(&Box::new(7u16))?, 7);
+    assert_eq!(*round_trip(&Arc::new(7u32))?, 7);
+    assert_eq!(round_trip(&Cow::Borrowed("text"))?, "text");
+    assert_eq!(
+        round_trip(&(1u8, string_name!("a"), -3i64))?,
+        (1, string_name!("a"), -3)
+    );
+    assert_eq!(round_trip(&Ok::<u8, u64>(3))?, Ok(3));
+    assert_eq!(round_trip(&Err::<u8, u64>(4))?, Err(4));
+    let map = BTreeMap::from([(2u8, string_name!("b")), (1u8, string_name!("a"))]);
+    assert_eq!(round_trip(&map)?, map);
+    let set = HashSet::from([1u32, 2, 3]);
+    assert_eq!(round_trip(&set)?, set);
+    let set = IndexSet::from([3u32, 1, 2]);
+    assert!(round_trip(&set)?.iter().eq(set.iter()));
+    Ok(())
+}
+
+#[test]
+fn test_bool_out_of_range() {
+    let interner = StringInterner::default();
+    let mut reader = [2u8].as_slice();
+    assert!(bool::read_from_file(&mut Decoder::new(&interner, &mut reader)).is_err());
+}
+
+#[test]
+fn test_string_heap() -> global::Result<()> {
+    let strings = ["a\0b".to_owned(), "\0".to_owned(), "a".to_owned()];
+    let mut file = File::default();
+    strings.write_to_file(&mut file.encoder())?;
+    let mut file = File::new(file.to_bytes()?)?;
+    assert_eq!(<[String; 3]>::read_from_file(&mut file.decoder())?, strings);
+
+    let heap = file.interner().to_bytes();
+    assert_eq!(heap.len() % 8, 0);
+    assert!(StringInterner::new(&heap[..heap.len() - 8]).is_err());
+    let mut duplicated = heap.clone();
+    duplicated.extend_from_slice(&[0; 8]);
+    assert!(StringInterner::new(duplicated).is_err());
     Ok(())
 }
 
@@ -156,3 +1705,348 @@ fn test_get_only() -> global::Result<()> {
     dbg!(&assem);
     Ok(())
 }
+
+#[test]
+fn test_assembly_refs() -> global::Result<()> {
+    let mut assem = test_assembly()?;
+    assert!(
+        assem
+            .referenced_assemblies()
+            .into_iter()
+            .eq([&string_name!("!")])
+    );
+    assert_eq!(
+        assem.assembly_refs(),
+        vec![AssemblyRef::unversioned(string_name!("!"))]
+    );
+
+    let class = test_class(&mut assem);
+    class.fields.insert(
+        string_name!("list"),
+        class::Field::new(
+            string_name!("list"),
+            FieldAttr::new(Visibility::Public, BitFlags::empty()),
+            StringTypeReference::WithGeneric {
+                assem: string_name!("!"),
+                ty: string_name!("System.Array`1"),
+                type_vars: Arc::new(indexmap! {
+                    string_name!("@T") => StringTypeReference::make_static_single("Collections", "Collections.Item"),
+                }),
+            },
+        ),
+    );
+    let pinned = AssemblyRef::new(
+        string_name!("Collections"),
+        Some(AssemblyVersion::new(1, 2, 0, 0)),
+        Some(AssemblyIdentity::PublicKeyToken([7; 8])),
+    );
+    assert_eq!(assem.pin_assembly_ref(pinned.clone()), Ok(()));
+    assert_eq!(
+        assem.pin_assembly_ref(AssemblyRef::unversioned(string_name!("!"))),
+        Ok(())
+    );
+    assert_eq!(assem.assembly_ref(&string_name!("!")), None);
+    for name in ["Test", "Elsewhere"] {
+        let name = StringName::from_static_str(name);
+        assert_eq!(
+            assem.pin_assembly_ref(AssemblyRef::new(
+                name.clone(),
+                Some(AssemblyVersion::new(1, 0, 0, 0)),
+                None,
+            )),
+            Err(ValidationError::UnreferencedAssembly { assembly: name })
+        );
+    }
+    assert_eq!(
+        assem.assembly_refs(),
+        vec![AssemblyRef::unversioned(string_name!("!")), pinned.clone(),]
+    );
+    assert_eq!(pinned.version().unwrap().to_string(), "1.2.0.0");
+
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten, assem);
+    assert_eq!(assem_gotten.assembly_refs(), assem.assembly_refs());
+    assert_eq!(
+        assem_gotten.assembly_ref(&string_name!("Collections")),
+        Some(&pinned)
+    );
+
+    let write_with_refs = |refs: Vec<AssemblyRef>| -> global::Result<Vec<u8>> {
+        let mut file = File::default();
+        let mut encoder = file.encoder();
+        assem.name().write_to_file(&mut encoder)?;
+        assem.type_defs().write_to_file(&mut encoder)?;
+        assem.implementations().write_to_file(&mut encoder)?;
+        assem
+            .custom_attributes()
+            .to_vec()
+            .write_to_file(&mut encoder)?;
+        refs.write_to_file(&mut encoder)?;
+        file.to_bytes()
+    };
+    let unsorted = write_with_refs(vec![
+        pinned.clone(),
+        AssemblyRef::unversioned(string_name!("!")),
+    ])?;
+    assert_eq!(Assembly::from_bytes(unsorted)?, assem);
+    let duplicated = write_with_refs(vec![
+        pinned.clone(),
+        AssemblyRef::unversioned(string_name!("Collections")),
+    ])?;
+    assert!(Assembly::from_bytes(duplicated).is_err());
+    for name in ["Test", "Elsewhere"] {
+        let unreferenced = write_with_refs(vec![
+            pinned.clone(),
+            AssemblyRef::unversioned(StringName::from_static_str(name)),
+        ])?;
+        assert!(Assembly::from_bytes(unreferenced).is_err());
+    }
+
+    let class = test_class(&mut assem);
+    class.fields.shift_remove(&string_name!("list"));
+    assert_eq!(
+        assem.assembly_refs(),
+        vec![AssemblyRef::unversioned(string_name!("!"))]
+    );
+
+    let class = test_class(&mut assem);
+    let mut property = Property::new(
+        string_name!("Items"),
+        StringTypeReference::core_static_single_type("System.Object"),
+        None,
+        None,
+        BitFlags::empty(),
+    );
+    property.custom_attributes_mut().push(CustomAttribute::new(
+        StringTypeReference::make_static_single("Annotations", "Annotations.Tag"),
+        StringMethodReference::Single(string_name!(".ctor()")),
+        vec![],
+        indexmap! {},
+    ));
+    class.properties.insert(string_name!("Items"), property);
+    class.methods.insert(
+        string_name!("Sort()"),
+        Method::new(
+            string_name!("Sort()"),
+            MethodAttr::new(Visibility::Public, BitFlags::empty(), 1),
+            vec![StringInstruction::InstanceCall {
+                val: 0,
+                method: StringMethodReference::WithGeneric {
+                    name: string_name!("Sort`1()"),
+                    type_vars: Arc::new(indexmap! {
+                        string_name!("@T") => StringTypeReference::make_static_single("Comparers", "Comparers.Key"),
+                    }),
+                },
+                args: vec![],
+                ret_at: 0,
+            }],
+            StringTypeReference::core_static_single_type("System.Void"),
+            vec![],
+            IndexMap::new(),
+        ),
+    );
+    assert!(assem.referenced_assemblies().into_iter().eq([
+        &string_name!("!"),
+        &string_name!("Annotations"),
+        &string_name!("Comparers"),
+    ]));
+    Ok(())
+}
+
+/// A method reference whose only mention of another assembly is its type argument.
+fn generic_method_ref() -> StringMethodReference {
+    StringMethodReference::WithGeneric {
+        name: string_name!("Create`1()"),
+        type_vars: Arc::new(indexmap! {
+            string_name!("@T") => StringTypeReference::make_static_single("Generic", "Generic.Item"),
+        }),
+    }
+}
+
+/// Checks that `edit` makes the test assembly reference `Generic` and that a pin for it
+/// survives a round trip.
+fn assert_pins_generic(edit: impl FnOnce(&mut ClassDef)) -> global::Result<()> {
+    let mut assem = test_assembly()?;
+    edit(test_class(&mut assem));
+    let generic = string_name!("Generic");
+    assert!(assem.referenced_assemblies().contains(&&generic));
+    let pinned = AssemblyRef::new(
+        generic.clone(),
+        Some(AssemblyVersion::new(2, 0, 0, 0)),
+        None,
+    );
+    assert_eq!(assem.pin_assembly_ref(pinned.clone()), Ok(()));
+    let assem_gotten = Assembly::from_bytes(assem.to_file_bytes()?)?;
+    assert_eq!(assem_gotten.assembly_ref(&generic), Some(&pinned));
+    Ok(())
+}
+
+#[test]
+fn test_assembly_refs_new_object_ctor() -> global::Result<()> {
+    assert_pins_generic(|class| {
+        class.methods.insert(
+            string_name!("Make()"),
+            Method::new(
+                string_name!("Make()"),
+                MethodAttr::new(Visibility::Public, BitFlags::empty(), 1),
+                vec![StringInstruction::NewObject {
+                    ty: StringTypeReference::core_static_single_type("System.Object"),
+                    ctor_name: generic_method_ref(),
+                    args: vec![],
+                    register_addr: 0,
+                }],
+                StringTypeReference::core_static_single_type("System.Void"),
+                vec![],
+                IndexMap::new(),
+            ),
+        );
+    })
+}
+
+#[test]
+fn test_assembly_refs_attribute_ctor() -> global::Result<()> {
+    assert_pins_generic(|class| {
+        class.custom_attributes.push(CustomAttribute::new(
+            StringTypeReference::core_static_single_type("System.ObsoleteAttribute"),
+            generic_method_ref(),
+            vec![],
+            indexmap! {},
+        ));
+    })
+}
+
+#[test]
+fn test_assembly_refs_override_declaration() -> global::Result<()> {
+    assert_pins_generic(|class| {
+        class.overrides.push(MethodOverride::new(
+            StringTypeReference::core_static_single_type("System.Object"),
+            generic_method_ref(),
+            StringMethodReference::Single(string_name!("Main()")),
+        ));
+    })
+}
+
+#[test]
+fn test_assembly_refs_override_implementation() -> global::Result<()> {
+    assert_pins_generic(|class| {
+        class.overrides.push(MethodOverride::new(
+            StringTypeReference::core_static_single_type("System.Object"),
+            StringMethodReference::Single(string_name!("ToString()")),
+            generic_method_ref(),
+        ));
+    })
+}
+
+#[test]
+fn test_assembly_refs_property_accessors() -> global::Result<()> {
+    let property = |getter, setter| {
+        Property::new(
+            string_name!("Value"),
+            StringTypeReference::core_static_single_type("System.Object"),
+            getter,
+            setter,
+            BitFlags::empty(),
+        )
+    };
+    assert_pins_generic(|class| {
+        class.properties.insert(
+            string_name!("Value"),
+            property(Some(generic_method_ref()), None),
+        );
+    })?;
+    assert_pins_generic(|class| {
+        class.properties.insert(
+            string_name!("Value"),
+            property(None, Some(generic_method_ref())),
+        );
+    })
+}
+
+#[test]
+fn test_assembly_refs_event_accessors() -> global::Result<()> {
+    let plain = || StringMethodReference::Single(string_name!("Main()"));
+    let event = |add, remove, raise| {
+        Event::new(
+            string_name!("Changed"),
+            StringTypeReference::make_static_single("Test", "Test.Callback"),
+            add,
+            remove,
+            raise,
+            BitFlags::empty(),
+        )
+    };
+    for event in [
+        event(generic_method_ref(), plain(), None),
+        event(plain(), generic_method_ref(), None),
+        event(plain(), plain(), Some(generic_method_ref())),
+    ] {
+        assert_pins_generic(|class| {
+            class.events.insert(string_name!("Changed"), event);
+        })?;
+    }
+    Ok(())
+}
+
+#[test]
+fn test_validate_interface_and_implementation_methods() -> global::Result<()> {
+    let too_few = |method: &str| ValidationError::TooFewRegisters {
+        method: StringName::from_string(method.to_owned()),
+        register_len: 1,
+        required: 4,
+    };
+    let body = |m: MethodBuilder| {
+        m.instruction(StringInstruction::LoadTrue { register_addr: 3 })
+            .registers(1)
+    };
+    let assem = AssemblyBuilder::new("Test")
+        .interface("Test.IGreeter")
+        .method("Greet", body)
+        .finish()
+        .build()?;
+    assert_eq!(assem.validate(), Err(too_few("Greet()")));
+
+    let assem = AssemblyBuilder::new("Test")
+        .interface("Test.IGreeter")
+        .method("Greet", |m| m)
+        .finish()
+        .class("Test.Test")
+        .implements("[Test]Test.IGreeter", |i| i.method("Greet", body))
+        .finish()
+        .build()?;
+    assert_eq!(assem.validate(), Err(too_few("Greet()")));
+    Ok(())
+}
+
+#[test]
+fn test_assembly_builder_rejects_interface_members() {
+    let interface = || AssemblyBuilder::new("Test").interface("Test.IGreeter");
+    assert!(
+        interface()
+            .extends("[!]System.Object")
+            .finish()
+            .build()
+            .is_err()
+    );
+    assert!(
+        interface()
+            .field("name", "[!]System.String")
+            .finish()
+            .build()
+            .is_err()
+    );
+    assert!(
+        interface()
+            .static_field("count", "[!]System.Int32")
+            .finish()
+            .build()
+            .is_err()
+    );
+    assert!(
+        interface()
+            .implements("[Test]Test.IOther", |i| i)
+            .finish()
+            .build()
+            .is_err()
+    );
+    assert!(interface().method("Greet", |m| m).finish().build().is_ok());
+}
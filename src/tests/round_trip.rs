@@ -0,0 +1,762 @@
+use crate::assembly::{Assembly, Header};
+use crate::core::File;
+use crate::implement::Implementation;
+use crate::method::Method;
+use crate::traits::{ReadFromFile, WriteToFile};
+use crate::ty::assembly_ref::{AssemblyIdentity, AssemblyRef, AssemblyVersion};
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::class::ClassDef;
+use crate::ty::constant::ConstantValue;
+use crate::ty::delegate::DelegateDef;
+use crate::ty::r#enum::{EnumDef, EnumMember, EnumUnderlyingType};
+use crate::ty::event::Event;
+use crate::ty::exception::{ExceptionClause, HandlerKind, InstructionRange};
+use crate::ty::field::Field;
+use crate::ty::interface::InterfaceDef;
+use crate::ty::layout::{LayoutKind, StructLayout};
+use crate::ty::local::LocalVariable;
+use crate::ty::nesting::{NestedVisibility, Nesting};
+use crate::ty::overrides::MethodOverride;
+use crate::ty::parameter::Parameter;
+use crate::ty::property::Property;
+use crate::ty::r#struct::StructDef;
+use crate::ty::{GenericBinding, TypeDef, Variance};
+use enumflags2::{BitFlag, BitFlags};
+use global::attrs::{FieldAttr, MethodAttr, TypeAttr, TypeSpecificAttr, Visibility};
+use global::instruction::StringInstruction;
+use global::num_enum::TryFromPrimitive;
+use global::{IndexMap, IndexSet, StringMethodReference, StringName, StringTypeReference};
+use proptest::collection::{btree_map, hash_map, hash_set, vec};
+use proptest::option;
+use proptest::prelude::*;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+fn encode<T: WriteToFile>(val: &T) -> Vec<u8> {
+    let mut file = File::default();
+    val.write_to_file(&mut file.encoder()).unwrap();
+    assert_eq!(val.encoded_size(file.interner()), file.data.get_ref().len());
+    file.to_bytes().unwrap()
+}
+
+fn decode<T: ReadFromFile>(bytes: &[u8]) -> T {
+    let mut file = File::new(bytes).unwrap();
+    let val = T::read_from_file(&mut file.decoder()).unwrap();
+    assert_eq!(
+        file.data.position(),
+        file.data.get_ref().len() as u64,
+        "trailing bytes after decoding {}",
+        std::any::type_name::<T>()
+    );
+    val
+}
+
+fn assert_round_trip<T: ReadFromFile + WriteToFile + PartialEq + Debug>(val: T) {
+    assert_eq!(decode::<T>(&encode(&val)), val);
+}
+
+fn string_name() -> impl Strategy<Value = StringName> {
+    "[A-Za-z_][A-Za-z0-9_.]{0,8}".prop_map(StringName::from_string)
+}
+
+fn single_type_ref() -> impl Strategy<Value = StringTypeReference> {
+    (
+        "!|[A-Z][a-z]{0,5}",
+        "[A-Z][a-z]{0,5}(\\.[A-Z][a-z]{0,5}){0,2}",
+    )
+        .prop_map(|(assem, ty)| {
+            StringTypeReference::from_string_repr(format!("[{assem}]{ty}")).unwrap()
+        })
+}
+
+fn type_ref() -> impl Strategy<Value = StringTypeReference> {
+    prop_oneof![
+        single_type_ref(),
+        (single_type_ref(), single_type_ref()).prop_map(|(outer, arg)| {
+            StringTypeReference::from_string_repr(format!(
+                "{}`1[@T:{}]",
+                outer.string_name_repr().as_str(),
+                arg.string_name_repr().as_str(),
+            ))
+            .unwrap()
+        }),
+    ]
+}
+
+fn method_ref() -> impl Strategy<Value = StringMethodReference> {
+    "[A-Z][a-z]{0,5}\\(\\)".prop_map(|s| StringMethodReference::Single(StringName::from_string(s)))
+}
+
+fn visibility() -> impl Strategy<Value = Visibility> {
+    (0u8..16).prop_filter_map("not a visibility", |x| {
+        Visibility::try_from_primitive(x).ok()
+    })
+}
+
+fn flags<T: BitFlag + Debug>() -> impl Strategy<Value = BitFlags<T>> {
+    let all = BitFlags::<T>::all().iter().collect::<Vec<_>>();
+    let len = all.len();
+    proptest::sample::subsequence(all, 0..=len).prop_map(BitFlags::from_iter)
+}
+
+fn type_attr() -> impl Strategy<Value = TypeAttr> {
+    let specific = prop_oneof![
+        flags().prop_map(TypeSpecificAttr::Class),
+        flags().prop_map(TypeSpecificAttr::Struct),
+        flags().prop_map(TypeSpecificAttr::Interface),
+    ];
+    (visibility(), specific).prop_map(|(vis, specific)| TypeAttr::new(vis, specific))
+}
+
+fn method_attr() -> impl Strategy<Value = MethodAttr> {
+    (visibility(), flags(), any::<u16>())
+        .prop_map(|(vis, flags, register_len)| MethodAttr::new(vis, flags, register_len as _))
+}
+
+fn field_attr() -> impl Strategy<Value = FieldAttr> {
+    (visibility(), flags()).prop_map(|(vis, flags)| FieldAttr::new(vis, flags))
+}
+
+fn registers() -> impl Strategy<Value = Vec<u8>> {
+    vec(any::<u8>(), 0..4)
+}
+
+#[allow(deprecated)]
+fn load_u8_const() -> impl Strategy<Value = StringInstruction> {
+    (0u8..6, any::<u8>()).prop_map(|(val, r)| {
+        let register_addr = r as _;
+        match val {
+            0 => StringInstruction::Load_u8_0 { register_addr },
+            1 => StringInstruction::Load_u8_1 { register_addr },
+            2 => StringInstruction::Load_u8_2 { register_addr },
+            3 => StringInstruction::Load_u8_3 { register_addr },
+            4 => StringInstruction::Load_u8_4 { register_addr },
+            _ => StringInstruction::Load_u8_5 { register_addr },
+        }
+    })
+}
+
+fn instruction() -> impl Strategy<Value = StringInstruction> {
+    prop_oneof![
+        load_u8_const(),
+        any::<u8>().prop_map(|r| StringInstruction::LoadTrue {
+            register_addr: r as _
+        }),
+        any::<u8>().prop_map(|r| StringInstruction::LoadFalse {
+            register_addr: r as _
+        }),
+        (any::<u8>(), any::<u8>()).prop_map(|(r, val)| StringInstruction::Load_u8 {
+            register_addr: r as _,
+            val: val as _,
+        }),
+        (any::<u8>(), any::<u64>()).prop_map(|(r, val)| StringInstruction::Load_u64 {
+            register_addr: r as _,
+            val: val as _,
+        }),
+        (any::<u8>(), method_ref(), registers(), any::<u8>()).prop_map(
+            |(val, method, args, ret_at)| StringInstruction::InstanceCall {
+                val: val as _,
+                method,
+                args: args.into_iter().map(|x| x as _).collect(),
+                ret_at: ret_at as _,
+            }
+        ),
+        (type_ref(), method_ref(), registers(), any::<u8>()).prop_map(
+            |(ty, method, args, ret_at)| StringInstruction::StaticCall {
+                ty,
+                method,
+                args: args.into_iter().map(|x| x as _).collect(),
+                ret_at: ret_at as _,
+            }
+        ),
+        (type_ref(), method_ref(), registers(), any::<u8>()).prop_map(
+            |(ty, ctor_name, args, r)| StringInstruction::NewObject {
+                ty,
+                ctor_name,
+                args: args.into_iter().map(|x| x as _).collect(),
+                register_addr: r as _,
+            }
+        ),
+        (any::<u8>(), any::<u8>()).prop_map(|(r, arg)| StringInstruction::LoadArg {
+            register_addr: r as _,
+            arg: arg as _,
+        }),
+        any::<u8>().prop_map(|r| StringInstruction::LoadAllArgsAsArray {
+            register_addr: r as _
+        }),
+        (any::<u8>(), type_ref(), string_name()).prop_map(|(r, ty, name)| {
+            StringInstruction::LoadStatic {
+                register_addr: r as _,
+                ty,
+                name,
+            }
+        }),
+        any::<u8>().prop_map(|r| StringInstruction::ReturnVal {
+            register_addr: r as _
+        }),
+        (any::<u8>(), string_name()).prop_map(|(r, field)| StringInstruction::SetField {
+            register_addr: r as _,
+            field,
+        }),
+    ]
+}
+
+fn generic_binding() -> impl Strategy<Value = GenericBinding> {
+    let variance = prop_oneof![
+        Just(Variance::Invariant),
+        Just(Variance::Covariant),
+        Just(Variance::Contravariant),
+    ];
+    (
+        vec(type_ref(), 0..3),
+        option::of(type_ref()),
+        variance,
+        flags(),
+        vec(string_name(), 0..2),
+    )
+        .prop_map(
+            |(interfaces, parent, variance, special_constraints, type_var_constraints)| {
+                let mut binding = GenericBinding::new(interfaces, parent);
+                *binding.variance_mut() = variance;
+                *binding.special_constraints_mut() = special_constraints;
+                *binding.type_var_constraints_mut() = type_var_constraints;
+                binding
+            },
+        )
+}
+
+fn type_vars() -> impl Strategy<Value = IndexMap<StringName, GenericBinding>> {
+    vec(("@[A-Z]", generic_binding()), 0..3).prop_map(|vars| {
+        vars.into_iter()
+            .map(|(name, binding)| (StringName::from_string(name), binding))
+            .collect()
+    })
+}
+
+fn constant_value() -> impl Strategy<Value = ConstantValue> {
+    prop_oneof![
+        Just(ConstantValue::Null),
+        any::<bool>().prop_map(ConstantValue::Bool),
+        any::<i8>().prop_map(ConstantValue::I8),
+        any::<i16>().prop_map(ConstantValue::I16),
+        any::<i32>().prop_map(ConstantValue::I32),
+        any::<i64>().prop_map(ConstantValue::I64),
+        any::<u8>().prop_map(ConstantValue::U8),
+        any::<u16>().prop_map(ConstantValue::U16),
+        any::<u32>().prop_map(ConstantValue::U32),
+        any::<u64>().prop_map(ConstantValue::U64),
+        any::<f32>().prop_map(ConstantValue::F32),
+        any::<f64>().prop_map(ConstantValue::F64),
+        string_name().prop_map(ConstantValue::String),
+        vec(any::<u8>(), 0..8).prop_map(ConstantValue::Blob),
+    ]
+}
+
+fn custom_attributes() -> impl Strategy<Value = Vec<CustomAttribute>> {
+    let attribute = (
+        type_ref(),
+        method_ref(),
+        vec(constant_value(), 0..3),
+        vec((string_name(), constant_value()), 0..2),
+    )
+        .prop_map(|(attr_type, ctor, positional_args, named_args)| {
+            CustomAttribute::new(
+                attr_type,
+                ctor,
+                positional_args,
+                named_args.into_iter().collect(),
+            )
+        });
+    vec(attribute, 0..2)
+}
+
+fn parameter() -> impl Strategy<Value = Parameter> {
+    (
+        string_name(),
+        type_ref(),
+        flags(),
+        option::of(constant_value()),
+        custom_attributes(),
+    )
+        .prop_map(|(name, ty, flags, default, custom_attributes)| {
+            let mut parameter = Parameter::new(name, ty, flags, default);
+            *parameter.custom_attributes_mut() = custom_attributes;
+            parameter
+        })
+}
+
+fn local() -> impl Strategy<Value = LocalVariable> {
+    (type_ref(), option::of(string_name()), flags())
+        .prop_map(|(ty, name, flags)| LocalVariable::new(ty, name, flags))
+}
+
+fn instruction_range() -> impl Strategy<Value = InstructionRange> {
+    (any::<u64>(), any::<u64>()).prop_map(|(start, end)| InstructionRange::new(start, end))
+}
+
+fn exception_clause() -> impl Strategy<Value = ExceptionClause> {
+    let kind = prop_oneof![
+        type_ref().prop_map(HandlerKind::Catch),
+        instruction_range().prop_map(HandlerKind::Filter),
+        Just(HandlerKind::Finally),
+        Just(HandlerKind::Fault),
+    ];
+    (instruction_range(), instruction_range(), kind).prop_map(|(try_range, handler_range, kind)| {
+        ExceptionClause::new(try_range, handler_range, kind)
+    })
+}
+
+fn method() -> impl Strategy<Value = Method> {
+    (
+        string_name(),
+        method_attr(),
+        vec(instruction(), 0..6),
+        type_ref(),
+        vec(parameter(), 0..3),
+        type_vars(),
+        custom_attributes(),
+        vec(local(), 0..3),
+        vec(exception_clause(), 0..3),
+        flags(),
+    )
+        .prop_map(
+            |(
+                name,
+                attr,
+                instructions,
+                ret_type,
+                params,
+                type_vars,
+                custom_attributes,
+                locals,
+                exception_clauses,
+                slot_flags,
+            )| {
+                let mut method =
+                    Method::with_params(name, attr, instructions, ret_type, params, type_vars);
+                *method.custom_attributes_mut() = custom_attributes;
+                *method.locals_mut() = locals;
+                *method.exception_clauses_mut() = exception_clauses;
+                *method.slot_flags_mut() = slot_flags;
+                method
+            },
+        )
+}
+
+fn methods() -> impl Strategy<Value = IndexMap<StringName, Method>> {
+    vec(method(), 0..3)
+        .prop_map(|methods| methods.into_iter().map(|m| (m.name().clone(), m)).collect())
+}
+
+fn field() -> impl Strategy<Value = Field> {
+    (
+        string_name(),
+        field_attr(),
+        type_ref(),
+        option::of(constant_value()),
+        custom_attributes(),
+        option::of(any::<u32>()),
+    )
+        .prop_map(|(name, attr, ty, constant, custom_attributes, offset)| {
+            let mut field = Field::new(name, attr, ty);
+            *field.constant_mut() = constant;
+            *field.custom_attributes_mut() = custom_attributes;
+            *field.offset_mut() = offset;
+            field
+        })
+}
+
+fn fields() -> impl Strategy<Value = IndexMap<StringName, Field>> {
+    vec(field(), 0..3)
+        .prop_map(|fields| fields.into_iter().map(|f| (f.name().clone(), f)).collect())
+}
+
+fn properties() -> impl Strategy<Value = IndexMap<StringName, Property>> {
+    let property = (
+        string_name(),
+        type_ref(),
+        option::of(method_ref()),
+        option::of(method_ref()),
+        flags(),
+        custom_attributes(),
+    )
+        .prop_map(
+            |(name, ty, getter, setter, impl_flags, custom_attributes)| {
+                let mut property = Property::new(name, ty, getter, setter, impl_flags);
+                *property.custom_attributes_mut() = custom_attributes;
+                property
+            },
+        );
+    vec(property, 0..3).prop_map(|properties| {
+        properties
+            .into_iter()
+            .map(|p| (p.name().clone(), p))
+            .collect()
+    })
+}
+
+fn events() -> impl Strategy<Value = IndexMap<StringName, Event>> {
+    let event = (
+        string_name(),
+        type_ref(),
+        method_ref(),
+        method_ref(),
+        option::of(method_ref()),
+        flags(),
+        custom_attributes(),
+    )
+        .prop_map(
+            |(name, ty, add, remove, raise, impl_flags, custom_attributes)| {
+                let mut event = Event::new(name, ty, add, remove, raise, impl_flags);
+                *event.custom_attributes_mut() = custom_attributes;
+                event
+            },
+        );
+    vec(event, 0..3).prop_map(|events| events.into_iter().map(|e| (e.name().clone(), e)).collect())
+}
+
+fn method_override() -> impl Strategy<Value = MethodOverride> {
+    (type_ref(), method_ref(), method_ref()).prop_map(
+        |(declaring_type, declaration, implementation)| {
+            MethodOverride::new(declaring_type, declaration, implementation)
+        },
+    )
+}
+
+fn class_def() -> impl Strategy<Value = ClassDef> {
+    (
+        option::of(type_ref()),
+        vec(type_ref(), 0..3),
+        type_vars(),
+        type_attr(),
+        string_name(),
+        methods(),
+        fields(),
+        properties(),
+        events(),
+        custom_attributes(),
+        vec(method_override(), 0..3),
+    )
+        .prop_map(
+            |(
+                parent,
+                interfaces,
+                type_vars,
+                attr,
+                name,
+                methods,
+                fields,
+                properties,
+                events,
+                custom_attributes,
+                overrides,
+            )| {
+                let mut class_def = ClassDef::new(parent, type_vars, attr, name, methods, fields);
+                *class_def.interfaces_mut() = interfaces;
+                *class_def.properties_mut() = properties;
+                *class_def.events_mut() = events;
+                *class_def.custom_attributes_mut() = custom_attributes;
+                *class_def.overrides_mut() = overrides;
+                class_def
+            },
+        )
+}
+
+fn struct_layout() -> impl Strategy<Value = StructLayout> {
+    let kind = prop_oneof![
+        Just(LayoutKind::Auto),
+        Just(LayoutKind::Sequential),
+        Just(LayoutKind::Explicit),
+    ];
+    (kind, option::of(any::<u16>()), option::of(any::<u32>()))
+        .prop_map(|(kind, packing, size)| StructLayout::new(kind, packing, size))
+}
+
+fn struct_def() -> impl Strategy<Value = StructDef> {
+    (
+        option::of(type_ref()),
+        vec(type_ref(), 0..3),
+        type_vars(),
+        type_attr(),
+        string_name(),
+        methods(),
+        fields(),
+        properties(),
+        events(),
+        custom_attributes(),
+        struct_layout(),
+    )
+        .prop_map(
+            |(
+                parent,
+                interfaces,
+                type_vars,
+                attr,
+                name,
+                methods,
+                fields,
+                properties,
+                events,
+                custom_attributes,
+                layout,
+            )| {
+                let mut struct_def = StructDef::new(parent, type_vars, attr, name, methods, fields);
+                *struct_def.interfaces_mut() = interfaces;
+                *struct_def.properties_mut() = properties;
+                *struct_def.events_mut() = events;
+                *struct_def.custom_attributes_mut() = custom_attributes;
+                *struct_def.layout_mut() = layout;
+                struct_def
+            },
+        )
+}
+
+fn interface_def() -> impl Strategy<Value = InterfaceDef> {
+    (
+        vec(type_ref(), 0..3),
+        type_vars(),
+        type_attr(),
+        string_name(),
+        methods(),
+        custom_attributes(),
+    )
+        .prop_map(
+            |(super_interfaces, type_vars, attr, name, methods, custom_attributes)| {
+                let mut interface_def =
+                    InterfaceDef::new(super_interfaces, type_vars, attr, name, methods);
+                *interface_def.custom_attributes_mut() = custom_attributes;
+                interface_def
+            },
+        )
+}
+
+fn enum_def() -> impl Strategy<Value = EnumDef> {
+    let underlying = prop_oneof![
+        Just(EnumUnderlyingType::U8),
+        Just(EnumUnderlyingType::U16),
+        Just(EnumUnderlyingType::U32),
+        Just(EnumUnderlyingType::U64),
+        Just(EnumUnderlyingType::I8),
+        Just(EnumUnderlyingType::I16),
+        Just(EnumUnderlyingType::I32),
+        Just(EnumUnderlyingType::I64),
+    ];
+    let members = vec(
+        (string_name(), any::<i128>()).prop_map(|(name, value)| EnumMember::new(name, value)),
+        0..4,
+    );
+    (
+        type_attr(),
+        string_name(),
+        underlying,
+        members,
+        any::<bool>(),
+        custom_attributes(),
+    )
+        .prop_map(
+            |(attr, name, underlying, members, is_flags, custom_attributes)| {
+                let mut enum_def = EnumDef::new(attr, name, underlying, members, is_flags);
+                *enum_def.custom_attributes_mut() = custom_attributes;
+                enum_def
+            },
+        )
+}
+
+fn delegate_def() -> impl Strategy<Value = DelegateDef> {
+    (
+        type_vars(),
+        type_attr(),
+        string_name(),
+        vec(type_ref(), 0..3),
+        type_ref(),
+        custom_attributes(),
+    )
+        .prop_map(
+            |(type_vars, attr, name, args, ret_type, custom_attributes)| {
+                let mut delegate_def = DelegateDef::new(type_vars, attr, name, args, ret_type);
+                *delegate_def.custom_attributes_mut() = custom_attributes;
+                delegate_def
+            },
+        )
+}
+
+fn nesting() -> impl Strategy<Value = Nesting> {
+    let visibility = prop_oneof![
+        Just(NestedVisibility::Public),
+        Just(NestedVisibility::Private),
+        Just(NestedVisibility::Assembly),
+    ];
+    (string_name(), visibility)
+        .prop_map(|(enclosing, visibility)| Nesting::new(enclosing, visibility))
+}
+
+fn type_def() -> impl Strategy<Value = TypeDef> {
+    let type_def = prop_oneof![
+        class_def().prop_map(TypeDef::Class),
+        struct_def().prop_map(TypeDef::Struct),
+        interface_def().prop_map(TypeDef::Interface),
+        enum_def().prop_map(TypeDef::Enum),
+        delegate_def().prop_map(TypeDef::Delegate),
+    ];
+    (type_def, option::of(nesting())).prop_map(|(mut type_def, nesting)| {
+        *type_def.nesting_mut() = nesting;
+        type_def
+    })
+}
+
+fn implementation() -> impl Strategy<Value = Implementation> {
+    (type_ref(), option::of(type_ref()), methods())
+        .prop_map(|(ty, interface, methods)| Implementation::new(ty, interface, methods))
+}
+
+fn assembly_ref() -> impl Strategy<Value = AssemblyRef> {
+    let version = any::<[u16; 4]>().prop_map(|[major, minor, build, revision]| {
+        AssemblyVersion::new(major, minor, build, revision)
+    });
+    let identity = prop_oneof![
+        any::<[u8; 32]>().prop_map(AssemblyIdentity::ContentHash),
+        any::<[u8; 8]>().prop_map(AssemblyIdentity::PublicKeyToken),
+    ];
+    (string_name(), option::of(version), option::of(identity))
+        .prop_map(|(name, version, identity)| AssemblyRef::new(name, version, identity))
+}
+
+fn assembly() -> impl Strategy<Value = Assembly> {
+    (
+        string_name(),
+        hash_map(string_name(), type_def(), 0..4),
+        hash_map(string_name(), implementation(), 0..4),
+        custom_attributes(),
+        vec(assembly_ref(), 0..3),
+    )
+        .prop_map(
+            |(name, type_defs, implementations, custom_attributes, assembly_refs)| {
+                let mut assem = Assembly::default();
+                *assem.name_mut() = name;
+                *assem.type_defs_mut() = type_defs;
+                *assem.implementations_mut() = implementations;
+                *assem.custom_attributes_mut() = custom_attributes;
+                let names = assem
+                    .referenced_assemblies()
+                    .into_iter()
+                    .cloned()
+                    .collect::<Vec<_>>();
+                for (name, assembly_ref) in names.into_iter().zip(assembly_refs) {
+                    let pin = AssemblyRef::new(
+                        name,
+                        assembly_ref.version(),
+                        assembly_ref.identity().clone(),
+                    );
+                    assem.pin_assembly_ref(pin).unwrap();
+                }
+                assem
+            },
+        )
+}
+
+proptest! {
+    #[test]
+    fn primitives(a in any::<u8>(), b in any::<i16>(), c in any::<u32>(), d in any::<i64>(), e in any::<u128>()) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+        assert_round_trip(c);
+        assert_round_trip(d);
+        assert_round_trip(e);
+    }
+
+    #[test]
+    fn floats(a in any::<f32>().prop_filter("NaN", |x| !x.is_nan()), b in any::<f64>().prop_filter("NaN", |x| !x.is_nan())) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+    }
+
+    #[test]
+    fn scalars(a in any::<bool>(), b in any::<char>(), c in any::<usize>()) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+        assert_round_trip(c);
+    }
+
+    #[test]
+    fn strings(a in any::<String>(), b in string_name()) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+    }
+
+    #[test]
+    fn arrays(a in any::<[u8; 2]>(), b in [string_name(), string_name(), string_name()]) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+    }
+
+    #[test]
+    fn containers(
+        a in vec(any::<u32>(), 0..8),
+        b in option::of(string_name()),
+        c in hash_map(any::<u16>(), any::<i8>(), 0..8),
+        d in vec((string_name(), any::<u64>()), 0..8),
+        e in any::<Result<u8, i32>>(),
+        f in (any::<u8>(), any::<bool>(), string_name()),
+        g in btree_map(any::<i32>(), string_name(), 0..8),
+        h in hash_set(string_name(), 0..8),
+    ) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+        assert_round_trip(c);
+        assert_round_trip(d.iter().cloned().collect::<IndexMap<_, _>>());
+        assert_round_trip(d.into_iter().collect::<HashMap<_, _>>());
+        assert_round_trip(e);
+        assert_round_trip(f);
+        assert_round_trip(g);
+        assert_round_trip(h.iter().cloned().collect::<IndexSet<_>>());
+        assert_round_trip(h);
+    }
+
+    #[test]
+    fn pointers(a in any::<u32>(), b in string_name(), c in any::<String>()) {
+        assert_round_trip(Box::new(a));
+        assert_round_trip(Arc::new(b));
+        assert_round_trip(Cow::<str>::Owned(c));
+    }
+
+    #[test]
+    fn header(magic in any::<[u8; 2]>()) {
+        assert_round_trip(Header { magic });
+    }
+
+    #[test]
+    fn type_refs(a in type_ref(), b in method_ref()) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+    }
+
+    #[test]
+    fn attrs(a in type_attr(), b in method_attr(), c in field_attr(), d in visibility()) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+        assert_round_trip(c);
+        assert_round_trip(d);
+    }
+
+    #[test]
+    fn instructions(a in instruction()) {
+        assert_round_trip(a);
+    }
+
+    #[test]
+    fn metadata(a in method(), b in field(), c in generic_binding(), d in type_def(), e in implementation(), f in assembly_ref()) {
+        assert_round_trip(a);
+        assert_round_trip(b);
+        assert_round_trip(c);
+        assert_round_trip(d);
+        assert_round_trip(e);
+        assert_round_trip(f);
+    }
+
+    #[test]
+    fn assemblies(a in assembly()) {
+        assert_round_trip(a);
+    }
+}
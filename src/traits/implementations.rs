@@ -1,4 +1,5 @@
-use crate::core::File;
+use crate::Error;
+use crate::core::{Decoder, Encoder, StringInterner};
 use crate::traits::{ReadFromFile, WriteToFile};
 use const_for::const_for;
 use enumflags2::{BitFlag, BitFlags};
@@ -6,12 +7,14 @@ use global::attrs::{
     FieldAttr, MethodAttr, TypeAttr, TypeSpecificAttr, TypeSpecificAttrType, Visibility,
 };
 use global::instruction::{StringInstruction, StringInstructionType};
-use global::{IndexMap, StringMethodReference, StringName, StringTypeReference};
+use global::{IndexMap, IndexSet, StringMethodReference, StringName, StringTypeReference};
 use std::any::Any;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fmt::Debug;
 use std::hash::Hash;
 use std::mem::MaybeUninit;
+use std::sync::Arc;
 
 macro primitive_impl($($t:ty)+) {$(
     impl super::ToBytes for $t {
@@ -24,6 +27,22 @@ macro primitive_impl($($t:ty)+) {$(
             Self::from_le_bytes(bytes)
         }
     }
+    impl WriteToFile for $t {
+        fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+            file.writer().write_all(&super::ToBytes::to_bytes(self))?;
+            Ok(())
+        }
+        fn encoded_size(&self, _interner: &StringInterner) -> usize {
+            <Self as super::ToBytes>::SIZE
+        }
+    }
+    impl ReadFromFile for $t {
+        fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+            let mut buf = [0u8; <Self as super::FromBytes>::SIZE];
+            file.reader().read_exact(&mut buf)?;
+            Ok(super::FromBytes::from_bytes(buf))
+        }
+    }
 )+}
 
 primitive_impl! {
@@ -37,60 +56,185 @@ primitive_impl! {
     i32
     i64
     i128
+    f32
+    f64
+}
+
+/// Encoded as a single `u8`, `0` or `1`.
+impl ReadFromFile for bool {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        match u8::read_from_file(file)? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::EnumOutOfBounds(std::any::type_name::<Self>())
+                .throw()
+                .into()),
+        }
+    }
+}
+
+impl WriteToFile for bool {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        (*self as u8).write_to_file(file)
+    }
+    fn encoded_size(&self, _interner: &StringInterner) -> usize {
+        size_of::<u8>()
+    }
+}
+
+/// Encoded as its Unicode scalar value, a `u32`.
+impl ReadFromFile for char {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        Ok(char::try_from(u32::read_from_file(file)?)?)
+    }
+}
+
+impl WriteToFile for char {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        (*self as u32).write_to_file(file)
+    }
+    fn encoded_size(&self, _interner: &StringInterner) -> usize {
+        size_of::<u32>()
+    }
+}
+
+/// Encoded as a `u64` regardless of the target's pointer width.
+impl ReadFromFile for usize {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        Ok(usize::try_from(u64::read_from_file(file)?)?)
+    }
+}
+
+impl WriteToFile for usize {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        (*self as u64).write_to_file(file)
+    }
+    fn encoded_size(&self, _interner: &StringInterner) -> usize {
+        size_of::<u64>()
+    }
 }
 
 impl ReadFromFile for String {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let i = u64::read_from_file(file)?;
         Ok(file.get_string(i)?.to_owned())
     }
 }
 
 impl WriteToFile for str {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         let i = file.string_position_of(self)?;
         i.write_to_file(file)
     }
+    fn encoded_size(&self, _interner: &StringInterner) -> usize {
+        size_of::<u64>()
+    }
+}
+
+impl WriteToFile for String {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        self.as_str().write_to_file(file)
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.as_str().encoded_size(interner)
+    }
+}
+
+/// Encoded like `str`; always read back as [`Cow::Owned`].
+impl ReadFromFile for Cow<'_, str> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        Ok(Cow::Owned(String::read_from_file(file)?))
+    }
+}
+
+impl WriteToFile for Cow<'_, str> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        self.as_ref().write_to_file(file)
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.as_ref().encoded_size(interner)
+    }
 }
 
 impl ReadFromFile for StringName {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         Ok(Self::from_string(String::read_from_file(file)?))
     }
 }
 impl WriteToFile for StringName {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         self.as_str().write_to_file(file)
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.as_str().encoded_size(interner)
+    }
 }
 
 impl ReadFromFile for StringTypeReference {
     #[track_caller]
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         Ok(Self::from_string_repr(String::read_from_file(file)?)?)
     }
 }
 
 impl WriteToFile for StringTypeReference {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         self.string_name_repr().write_to_file(file)
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.string_name_repr().encoded_size(interner)
+    }
 }
 
 impl ReadFromFile for StringMethodReference {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         Self::from_string_repr(String::read_from_file(file)?)
     }
 }
 
 impl WriteToFile for StringMethodReference {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         self.string_name_repr().write_to_file(file)
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.string_name_repr().encoded_size(interner)
+    }
+}
+
+/// Encoded exactly like the boxed value.
+impl<T: ReadFromFile> ReadFromFile for Box<T> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        Ok(Box::new(T::read_from_file(file)?))
+    }
+}
+
+impl<T: WriteToFile + ?Sized> WriteToFile for Box<T> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        self.as_ref().write_to_file(file)
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.as_ref().encoded_size(interner)
+    }
+}
+
+/// Encoded exactly like the shared value; sharing is not preserved.
+impl<T: ReadFromFile> ReadFromFile for Arc<T> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        Ok(Arc::new(T::read_from_file(file)?))
+    }
+}
+
+impl<T: WriteToFile + ?Sized> WriteToFile for Arc<T> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        self.as_ref().write_to_file(file)
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.as_ref().encoded_size(interner)
+    }
 }
 
 impl<T: ReadFromFile> ReadFromFile for Vec<T> {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let i = u64::read_from_file(file)?;
         let mut vec = Vec::new();
         for _ in 0..i {
@@ -101,14 +245,21 @@ impl<T: ReadFromFile> ReadFromFile for Vec<T> {
 }
 
 impl<T: WriteToFile> WriteToFile for Vec<T> {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         (self.len() as u64).write_to_file(file)?;
         self.iter().try_for_each(|item| item.write_to_file(file))
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u64>()
+            + self
+                .iter()
+                .map(|item| item.encoded_size(interner))
+                .sum::<usize>()
+    }
 }
 
 impl<T: ReadFromFile, const N: usize> ReadFromFile for [T; N] {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let mut this = std::array::from_fn(|_| MaybeUninit::<T>::uninit());
         const_for! {
             i in (0..N) => {
@@ -119,9 +270,9 @@ impl<T: ReadFromFile, const N: usize> ReadFromFile for [T; N] {
     }
 }
 
+/// Encoded as its elements only; the length is part of the type.
 impl<T: WriteToFile, const N: usize> WriteToFile for [T; N] {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
-        (self.len() as u64).write_to_file(file)?;
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         const_for! {
             i in (0..N) => {
                 self[i].write_to_file(file)?;
@@ -129,10 +280,15 @@ impl<T: WriteToFile, const N: usize> WriteToFile for [T; N] {
         }
         Ok(())
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.iter()
+            .map(|item| item.encoded_size(interner))
+            .sum::<usize>()
+    }
 }
 
 impl<K: ReadFromFile + Eq + Hash, V: ReadFromFile> ReadFromFile for HashMap<K, V> {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let i = u64::read_from_file(file)?;
         let mut map = Self::with_capacity(i as usize);
         for _ in 0..i {
@@ -145,15 +301,103 @@ impl<K: ReadFromFile + Eq + Hash, V: ReadFromFile> ReadFromFile for HashMap<K, V
 }
 
 impl<K: WriteToFile, V: WriteToFile> WriteToFile for HashMap<K, V> {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         (self.len() as u64).write_to_file(file)?;
         self.iter()
             .try_for_each(|(k, v)| k.write_to_file(file).and_then(|_| v.write_to_file(file)))
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u64>()
+            + self
+                .iter()
+                .map(|(k, v)| k.encoded_size(interner) + v.encoded_size(interner))
+                .sum::<usize>()
+    }
+}
+
+/// Encoded like `HashMap`, entries in ascending key order.
+impl<K: ReadFromFile + Ord, V: ReadFromFile> ReadFromFile for BTreeMap<K, V> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        let i = u64::read_from_file(file)?;
+        let mut map = Self::new();
+        for _ in 0..i {
+            let k = K::read_from_file(file)?;
+            let v = V::read_from_file(file)?;
+            map.insert(k, v);
+        }
+        Ok(map)
+    }
+}
+
+impl<K: WriteToFile, V: WriteToFile> WriteToFile for BTreeMap<K, V> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        (self.len() as u64).write_to_file(file)?;
+        self.iter()
+            .try_for_each(|(k, v)| k.write_to_file(file).and_then(|_| v.write_to_file(file)))
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u64>()
+            + self
+                .iter()
+                .map(|(k, v)| k.encoded_size(interner) + v.encoded_size(interner))
+                .sum::<usize>()
+    }
+}
+
+/// Encoded like `Vec`, elements in iteration order.
+impl<T: ReadFromFile + Eq + Hash> ReadFromFile for HashSet<T> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        let i = u64::read_from_file(file)?;
+        let mut set = Self::with_capacity(i as usize);
+        for _ in 0..i {
+            set.insert(T::read_from_file(file)?);
+        }
+        Ok(set)
+    }
+}
+
+impl<T: WriteToFile> WriteToFile for HashSet<T> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        (self.len() as u64).write_to_file(file)?;
+        self.iter().try_for_each(|item| item.write_to_file(file))
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u64>()
+            + self
+                .iter()
+                .map(|item| item.encoded_size(interner))
+                .sum::<usize>()
+    }
+}
+
+/// Encoded like `Vec`, elements in insertion order.
+impl<T: ReadFromFile + Eq + Hash> ReadFromFile for IndexSet<T> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        let i = u64::read_from_file(file)?;
+        let mut set = Self::with_capacity(i as usize);
+        for _ in 0..i {
+            set.insert(T::read_from_file(file)?);
+        }
+        Ok(set)
+    }
+}
+
+impl<T: WriteToFile> WriteToFile for IndexSet<T> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        (self.len() as u64).write_to_file(file)?;
+        self.iter().try_for_each(|item| item.write_to_file(file))
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u64>()
+            + self
+                .iter()
+                .map(|item| item.encoded_size(interner))
+                .sum::<usize>()
+    }
 }
 
 impl<K: ReadFromFile + Eq + Hash, V: ReadFromFile> ReadFromFile for IndexMap<K, V> {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let i = u64::read_from_file(file)?;
         let mut map = Self::with_capacity(i as usize);
         for _ in 0..i {
@@ -166,11 +410,18 @@ impl<K: ReadFromFile + Eq + Hash, V: ReadFromFile> ReadFromFile for IndexMap<K,
 }
 
 impl<K: WriteToFile + Any, V: WriteToFile + Any> WriteToFile for IndexMap<K, V> {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         (self.len() as u64).write_to_file(file)?;
         self.iter()
             .try_for_each(|(k, v)| k.write_to_file(file).and_then(|_| v.write_to_file(file)))
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u64>()
+            + self
+                .iter()
+                .map(|(k, v)| k.encoded_size(interner) + v.encoded_size(interner))
+                .sum::<usize>()
+    }
 }
 
 impl<T: BitFlag> ReadFromFile for BitFlags<T>
@@ -178,7 +429,7 @@ where
     T::Numeric: ReadFromFile + Send + Sync + Debug,
     T: Send + Sync + Debug,
 {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let num = T::Numeric::read_from_file(file)?;
         Ok(Self::from_bits(num)?)
     }
@@ -188,14 +439,17 @@ impl<T: BitFlag> WriteToFile for BitFlags<T>
 where
     T::Numeric: WriteToFile,
 {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         let num = self.bits();
         num.write_to_file(file)
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        self.bits().encoded_size(interner)
+    }
 }
 
 impl<T: ReadFromFile> ReadFromFile for Option<T> {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let b = u8::read_from_file(file)?;
         if b == 1 {
             Ok(Some(T::read_from_file(file)?))
@@ -206,7 +460,7 @@ impl<T: ReadFromFile> ReadFromFile for Option<T> {
 }
 
 impl<T: WriteToFile> WriteToFile for Option<T> {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         match self {
             Some(val) => 1u8
                 .write_to_file(file)
@@ -214,10 +468,77 @@ impl<T: WriteToFile> WriteToFile for Option<T> {
             None => 0u8.write_to_file(file),
         }
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u8>() + self.as_ref().map_or(0, |val| val.encoded_size(interner))
+    }
+}
+
+/// A `u8` tag, `0` for `Ok` and `1` for `Err`, followed by the payload.
+impl<T: ReadFromFile, E: ReadFromFile> ReadFromFile for Result<T, E> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+        match u8::read_from_file(file)? {
+            0 => Ok(Ok(T::read_from_file(file)?)),
+            1 => Ok(Err(E::read_from_file(file)?)),
+            _ => Err(Error::EnumOutOfBounds(std::any::type_name::<Self>())
+                .throw()
+                .into()),
+        }
+    }
+}
+
+impl<T: WriteToFile, E: WriteToFile> WriteToFile for Result<T, E> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+        match self {
+            Ok(val) => 0u8
+                .write_to_file(file)
+                .and_then(|_| val.write_to_file(file)),
+            Err(err) => 1u8
+                .write_to_file(file)
+                .and_then(|_| err.write_to_file(file)),
+        }
+    }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u8>()
+            + match self {
+                Ok(val) => val.encoded_size(interner),
+                Err(err) => err.encoded_size(interner),
+            }
+    }
+}
+
+/// Tuples are encoded as their elements in order, with no header.
+macro tuple_impl($($($t:ident)+;)+) {$(
+    impl<$($t: ReadFromFile,)+> ReadFromFile for ($($t,)+) {
+        fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
+            Ok(($($t::read_from_file(file)?,)+))
+        }
+    }
+    impl<$($t: WriteToFile,)+> WriteToFile for ($($t,)+) {
+        #[allow(non_snake_case)]
+        fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
+            let ($($t,)+) = self;
+            $($t.write_to_file(file)?;)+
+            Ok(())
+        }
+        #[allow(non_snake_case)]
+        fn encoded_size(&self, interner: &StringInterner) -> usize {
+            let ($($t,)+) = self;
+            0 $(+ $t.encoded_size(interner))+
+        }
+    }
+)+}
+
+tuple_impl! {
+    A;
+    A B;
+    A B C;
+    A B C D;
+    A B C D E;
+    A B C D E F;
 }
 
 impl ReadFromFile for Visibility {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         Ok(
             <Self as global::num_enum::TryFromPrimitive>::try_from_primitive(
                 <Self as global::num_enum::TryFromPrimitive>::Primitive::read_from_file(file)?,
@@ -226,14 +547,17 @@ impl ReadFromFile for Visibility {
     }
 }
 impl WriteToFile for Visibility {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         let x: <Self as global::num_enum::TryFromPrimitive>::Primitive = (*self).into();
         x.write_to_file(file)
     }
+    fn encoded_size(&self, _interner: &StringInterner) -> usize {
+        size_of::<<Self as global::num_enum::TryFromPrimitive>::Primitive>()
+    }
 }
 
 impl ReadFromFile for TypeSpecificAttr {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let t: TypeSpecificAttrType = u8::read_from_file(file)?.try_into()?;
         match t {
             TypeSpecificAttrType::Class => Ok(Self::Class(ReadFromFile::read_from_file(file)?)),
@@ -246,7 +570,7 @@ impl ReadFromFile for TypeSpecificAttr {
 }
 
 impl WriteToFile for TypeSpecificAttr {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         (self.to_type() as u8).write_to_file(file)?;
         match self {
             TypeSpecificAttr::Class(flags) => flags.write_to_file(file),
@@ -254,19 +578,30 @@ impl WriteToFile for TypeSpecificAttr {
             TypeSpecificAttr::Interface(flags) => flags.write_to_file(file),
         }
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        size_of::<u8>()
+            + match self {
+                TypeSpecificAttr::Class(flags) => flags.encoded_size(interner),
+                TypeSpecificAttr::Struct(flags) => flags.encoded_size(interner),
+                TypeSpecificAttr::Interface(flags) => flags.encoded_size(interner),
+            }
+    }
 }
 
 macro rw_file_foreign($($t:ty: $($n:ident)+ ;)+) {$(
     impl WriteToFile for $t {
-        fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+        fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
             $(
                 self.$n ().write_to_file(file)?;
             )+
             Ok(())
         }
+        fn encoded_size(&self, interner: &StringInterner) -> usize {
+            0 $(+ self.$n ().encoded_size(interner))+
+        }
     }
     impl ReadFromFile for $t {
-        fn read_from_file(file: &mut File) -> global::Result<Self> {
+        fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
             Ok(Self::new(
                 $(
                     ReadFromFile::read_from_file(file)?,
@@ -290,7 +625,7 @@ many_rw_file_foreign! {
 }
 
 impl ReadFromFile for StringInstruction {
-    fn read_from_file(file: &mut File) -> global::Result<Self> {
+    fn read_from_file(file: &mut Decoder<'_>) -> global::Result<Self> {
         let t = StringInstructionType::try_from(u64::read_from_file(file)?)?;
         macro matcher($($i:ident => $(@$t_i:ident)|* $(|)?)+) {
             match t {
@@ -331,14 +666,17 @@ impl ReadFromFile for StringInstruction {
 }
 
 impl WriteToFile for StringInstructionType {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         let x: <Self as global::num_enum::TryFromPrimitive>::Primitive = (*self).into();
         x.write_to_file(file)
     }
+    fn encoded_size(&self, _interner: &StringInterner) -> usize {
+        size_of::<<Self as global::num_enum::TryFromPrimitive>::Primitive>()
+    }
 }
 
 impl WriteToFile for StringInstruction {
-    fn write_to_file(&self, file: &mut File) -> global::Result<()> {
+    fn write_to_file(&self, file: &mut Encoder<'_>) -> global::Result<()> {
         macro matcher($($i:ident => $(@$t_i:ident)|* $(|)?)+) {
             match self {
                 $(
@@ -376,4 +714,41 @@ impl WriteToFile for StringInstruction {
         }
         Ok(())
     }
+    fn encoded_size(&self, interner: &StringInterner) -> usize {
+        macro matcher($($i:ident => $(@$t_i:ident)|* $(|)?)+) {
+            match self {
+                $(
+                    ::global::instruction::StringInstruction::$i {
+                        $($t_i,)*
+                    } => {
+                        0 $(+ $t_i.encoded_size(interner))*
+                    }
+                )+
+            }
+        }
+        #[allow(deprecated)]
+        let fields_size = {
+            matcher! {
+                LoadTrue => @register_addr
+                LoadFalse => @register_addr
+                Load_u8 => @register_addr | @val
+                Load_u8_0 => @register_addr
+                Load_u8_1 => @register_addr
+                Load_u8_2 => @register_addr
+                Load_u8_3 => @register_addr
+                Load_u8_4 => @register_addr
+                Load_u8_5 => @register_addr
+                Load_u64 => @register_addr | @val
+                NewObject => @ty | @ctor_name | @args | @register_addr
+                InstanceCall => @val | @method | @args | @ret_at
+                StaticCall => @ty | @method | @args | @ret_at
+                LoadArg => @register_addr | @arg
+                LoadAllArgsAsArray => @register_addr
+                LoadStatic => @register_addr | @ty | @name
+                ReturnVal => @register_addr
+                SetField => @register_addr | @field
+            }
+        };
+        self.to_type().encoded_size(interner) + fields_size
+    }
 }
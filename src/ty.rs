@@ -1,28 +1,184 @@
+use crate::ty::attribute::CustomAttribute;
 use crate::ty::class::ClassDef;
+use crate::ty::delegate::DelegateDef;
+use crate::ty::r#enum::EnumDef;
+use crate::ty::event::Event;
+use crate::ty::field::Field;
+use crate::ty::interface::InterfaceDef;
+use crate::ty::method::Method;
+use crate::ty::nesting::Nesting;
+use crate::ty::property::Property;
+use crate::ty::signature::MethodSignature;
 use crate::ty::r#struct::StructDef;
-use global::getset::Getters;
-use global::{StringTypeReference, WithType};
+use crate::validation::ValidationError;
+use enumflags2::{BitFlags, bitflags};
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexMap, StringName, StringTypeReference, WithType};
 use proc_macros::{ReadFromFile, WriteToFile};
 
+pub mod assembly_ref;
+pub mod attribute;
 pub mod class;
+pub mod constant;
+pub mod delegate;
+pub mod r#enum;
+pub mod event;
+pub mod exception;
 pub mod field;
+pub mod interface;
+pub mod layout;
+pub mod local;
 pub mod method;
+pub mod nesting;
+pub mod overrides;
+pub mod parameter;
+pub mod property;
+pub mod registers;
+pub mod signature;
 pub mod r#struct;
 
-#[derive(Debug, Clone, WithType, ReadFromFile, WriteToFile)]
+#[derive(Debug, Clone, PartialEq, Eq, WithType, ReadFromFile, WriteToFile)]
 #[with_type(repr = u8)]
 #[with_type(derive = (Clone, Copy, ReadFromFile, WriteToFile))]
 #[allow(clippy::large_enum_variant)]
 pub enum TypeDef {
     Class(ClassDef),
     Struct(StructDef),
+    Interface(InterfaceDef),
+    Enum(EnumDef),
+    Delegate(DelegateDef),
 }
 
-#[derive(Clone, Debug, Getters, ReadFromFile, WriteToFile)]
+impl TypeDef {
+    pub fn as_delegate(&self) -> Option<&DelegateDef> {
+        match self {
+            Self::Delegate(delegate) => Some(delegate),
+            _ => None,
+        }
+    }
+    pub fn custom_attributes(&self) -> &[CustomAttribute] {
+        match self {
+            Self::Class(class_def) => class_def.custom_attributes(),
+            Self::Struct(struct_def) => struct_def.custom_attributes(),
+            Self::Interface(interface_def) => interface_def.custom_attributes(),
+            Self::Enum(enum_def) => enum_def.custom_attributes(),
+            Self::Delegate(delegate_def) => delegate_def.custom_attributes(),
+        }
+    }
+    pub fn nesting(&self) -> Option<&Nesting> {
+        match self {
+            Self::Class(class_def) => class_def.nesting(),
+            Self::Struct(struct_def) => struct_def.nesting(),
+            Self::Interface(interface_def) => interface_def.nesting(),
+            Self::Enum(enum_def) => enum_def.nesting(),
+            Self::Delegate(delegate_def) => delegate_def.nesting(),
+        }
+        .as_ref()
+    }
+    pub fn nesting_mut(&mut self) -> &mut Option<Nesting> {
+        match self {
+            Self::Class(class_def) => class_def.nesting_mut(),
+            Self::Struct(struct_def) => struct_def.nesting_mut(),
+            Self::Interface(interface_def) => interface_def.nesting_mut(),
+            Self::Enum(enum_def) => enum_def.nesting_mut(),
+            Self::Delegate(delegate_def) => delegate_def.nesting_mut(),
+        }
+    }
+    /// Whether other types may be declared inside this one.
+    pub fn can_nest(&self) -> bool {
+        matches!(self, Self::Class(_) | Self::Struct(_) | Self::Interface(_))
+    }
+    /// Implemented interfaces for classes and structs, extended ones for interfaces.
+    pub fn interfaces(&self) -> &[StringTypeReference] {
+        match self {
+            Self::Class(class_def) => class_def.interfaces(),
+            Self::Struct(struct_def) => struct_def.interfaces(),
+            Self::Interface(interface_def) => interface_def.super_interfaces(),
+            Self::Enum(_) | Self::Delegate(_) => &[],
+        }
+    }
+    pub fn parent(&self) -> Option<&StringTypeReference> {
+        match self {
+            Self::Class(class_def) => class_def.parent().as_ref(),
+            Self::Struct(struct_def) => struct_def.parent().as_ref(),
+            Self::Interface(_) | Self::Enum(_) | Self::Delegate(_) => None,
+        }
+    }
+    /// `None` for kinds that cannot declare methods.
+    pub fn methods(&self) -> Option<&IndexMap<StringName, Method>> {
+        match self {
+            Self::Class(class_def) => Some(class_def.methods()),
+            Self::Struct(struct_def) => Some(struct_def.methods()),
+            Self::Interface(interface_def) => Some(interface_def.methods()),
+            Self::Enum(_) | Self::Delegate(_) => None,
+        }
+    }
+    /// `None` for kinds that cannot declare properties.
+    pub fn properties(&self) -> Option<&IndexMap<StringName, Property>> {
+        match self {
+            Self::Class(class_def) => Some(class_def.properties()),
+            Self::Struct(struct_def) => Some(struct_def.properties()),
+            Self::Interface(_) | Self::Enum(_) | Self::Delegate(_) => None,
+        }
+    }
+    /// `None` for kinds that cannot declare events.
+    pub fn events(&self) -> Option<&IndexMap<StringName, Event>> {
+        match self {
+            Self::Class(class_def) => Some(class_def.events()),
+            Self::Struct(struct_def) => Some(struct_def.events()),
+            Self::Interface(_) | Self::Enum(_) | Self::Delegate(_) => None,
+        }
+    }
+    /// `None` for kinds that cannot declare fields.
+    pub fn fields(&self) -> Option<&IndexMap<StringName, Field>> {
+        match self {
+            Self::Class(class_def) => Some(class_def.fields()),
+            Self::Struct(struct_def) => Some(struct_def.fields()),
+            Self::Interface(_) | Self::Enum(_) | Self::Delegate(_) => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ReadFromFile, WriteToFile)]
+#[repr(u8)]
+pub enum Variance {
+    #[default]
+    Invariant,
+    /// `out T`: the type variable only appears in output positions.
+    Covariant,
+    /// `in T`: the type variable only appears in input positions.
+    Contravariant,
+}
+
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SpecialConstraint {
+    ReferenceType,
+    ValueType,
+    DefaultConstructor,
+    /// A value type containing no references, at any depth.
+    Unmanaged,
+}
+
+#[derive(
+    Clone, Debug, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
 #[getset(get = "pub")]
 pub struct GenericBinding {
     pub(crate) implemented_interfaces: Vec<StringTypeReference>,
     pub(crate) parent: Option<StringTypeReference>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    pub(crate) variance: Variance,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    pub(crate) special_constraints: BitFlags<SpecialConstraint>,
+    /// Other type variables this one must be assignable to, as in `where T: U`.
+    #[getset(get_mut = "pub")]
+    pub(crate) type_var_constraints: Vec<StringName>,
 }
 
 impl GenericBinding {
@@ -33,6 +189,130 @@ impl GenericBinding {
         Self {
             implemented_interfaces,
             parent,
+            variance: Variance::Invariant,
+            special_constraints: BitFlags::empty(),
+            type_var_constraints: Vec::new(),
+        }
+    }
+    /// Checks the binding of `var`; `in_scope` tells which type variables its constraints
+    /// may name.
+    pub fn validate(
+        &self,
+        var: &StringName,
+        in_scope: impl Fn(&StringName) -> bool,
+        allow_variance: bool,
+    ) -> Result<(), ValidationError> {
+        if !allow_variance && self.variance != Variance::Invariant {
+            return Err(ValidationError::VarianceNotAllowed { var: var.clone() });
+        }
+        let reference = self
+            .special_constraints
+            .contains(SpecialConstraint::ReferenceType);
+        let value = self
+            .special_constraints
+            .intersects(SpecialConstraint::ValueType | SpecialConstraint::Unmanaged);
+        if reference && value {
+            return Err(ValidationError::ConflictingGenericConstraints { var: var.clone() });
+        }
+        if let Some(constraint) = self
+            .type_var_constraints
+            .iter()
+            .find(|constraint| *constraint == var || !in_scope(constraint))
+        {
+            return Err(ValidationError::InvalidTypeVarConstraint {
+                var: var.clone(),
+                constraint: constraint.clone(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Name under which type references address the core library.
+pub(crate) const CORE_ASSEMBLY: &str = "!";
+
+/// Splits a reference to a named type into its assembly and type name; `None` for type
+/// variables.
+pub(crate) fn split_type_ref(ty: &StringTypeReference) -> Option<(&StringName, &StringName)> {
+    match ty {
+        StringTypeReference::Single { assem, ty }
+        | StringTypeReference::WithGeneric { assem, ty, .. } => Some((assem, ty)),
+        _ => None,
+    }
+}
+
+/// Validates `type_vars`, whose constraints may also name the variables of `enclosing`, and
+/// rejects constraint cycles such as `T: U, U: T`.
+pub(crate) fn validate_type_vars(
+    type_vars: &IndexMap<StringName, GenericBinding>,
+    enclosing: Option<&IndexMap<StringName, GenericBinding>>,
+    allow_variance: bool,
+) -> Result<(), ValidationError> {
+    let in_scope = |name: &StringName| {
+        type_vars.contains_key(name) || enclosing.is_some_and(|vars| vars.contains_key(name))
+    };
+    for (var, binding) in type_vars {
+        binding.validate(var, in_scope, allow_variance)?;
+    }
+    for var in type_vars.keys() {
+        let mut pending = vec![var];
+        let mut visited = Vec::new();
+        while let Some(name) = pending.pop() {
+            let Some(binding) = type_vars.get(name) else {
+                continue;
+            };
+            for constraint in binding.type_var_constraints() {
+                if constraint == var {
+                    return Err(ValidationError::TypeVarConstraintCycle { var: var.clone() });
+                }
+                if !visited.contains(&constraint) {
+                    visited.push(constraint);
+                    pending.push(constraint);
+                }
+            }
         }
     }
+    Ok(())
+}
+
+/// Stores `method` under the key derived from its [`MethodSignature`], returning the method
+/// it replaces.
+pub(crate) fn insert_method(
+    methods: &mut IndexMap<StringName, Method>,
+    method: Method,
+) -> Option<Method> {
+    methods.insert(MethodSignature::of(&method).key(), method)
+}
+
+/// Validates each method and its type variables, which may also name `type_vars`, the
+/// variables of the declaring type.
+pub(crate) fn validate_methods(
+    type_vars: &IndexMap<StringName, GenericBinding>,
+    methods: &IndexMap<StringName, Method>,
+) -> Result<(), ValidationError> {
+    for method in methods.values() {
+        validate_type_vars(method.type_vars(), Some(type_vars), false)?;
+        method.validate()?;
+    }
+    Ok(())
+}
+
+/// Validates the members classes and structs share: the type variables, the methods and the
+/// accessors of the properties and events, which must be methods of the type `ty`.
+pub(crate) fn validate_members(
+    ty: &StringName,
+    type_vars: &IndexMap<StringName, GenericBinding>,
+    methods: &IndexMap<StringName, Method>,
+    properties: &IndexMap<StringName, Property>,
+    events: &IndexMap<StringName, Event>,
+) -> Result<(), ValidationError> {
+    validate_type_vars(type_vars, None, false)?;
+    validate_methods(type_vars, methods)?;
+    for property in properties.values() {
+        property.validate(ty, methods)?;
+    }
+    for event in events.values() {
+        event.validate(ty, methods)?;
+    }
+    Ok(())
 }
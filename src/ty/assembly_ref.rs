@@ -0,0 +1,217 @@
+use crate::implement::Implementation;
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::class::Field;
+use crate::ty::exception::HandlerKind;
+use crate::ty::method::Method;
+use crate::ty::{GenericBinding, TypeDef, split_type_ref};
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::instruction::StringInstruction;
+use global::{IndexMap, StringMethodReference, StringName, StringTypeReference, WithType};
+use proc_macros::{ReadFromFile, WriteToFile};
+use std::collections::HashSet;
+use std::fmt::{self, Display, Formatter};
+
+#[derive(
+    ctor,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    Hash,
+    PartialOrd,
+    Ord,
+    CopyGetters,
+    ReadFromFile,
+    WriteToFile,
+)]
+#[getset(get_copy = "pub")]
+pub struct AssemblyVersion {
+    pub(crate) major: u16,
+    pub(crate) minor: u16,
+    pub(crate) build: u16,
+    pub(crate) revision: u16,
+}
+
+impl Display for AssemblyVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
+
+/// Pins a referenced assembly to exact contents or to a signing key.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, WithType, ReadFromFile, WriteToFile)]
+#[with_type(repr = u8)]
+#[with_type(derive = (Clone, Copy, ReadFromFile, WriteToFile))]
+pub enum AssemblyIdentity {
+    /// SHA-256 of the referenced assembly's file.
+    ContentHash([u8; 32]),
+    PublicKeyToken([u8; 8]),
+}
+
+/// One row of an assembly's dependency table; see
+/// [`Assembly::assembly_refs`](crate::assembly::Assembly::assembly_refs).
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct AssemblyRef {
+    pub(crate) name: StringName,
+    /// `None` accepts any version.
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    pub(crate) version: Option<AssemblyVersion>,
+    #[getset(get_mut = "pub")]
+    pub(crate) identity: Option<AssemblyIdentity>,
+}
+
+impl AssemblyRef {
+    pub fn unversioned(name: StringName) -> Self {
+        Self::new(name, None, None)
+    }
+    /// Whether the entry says more than the name the type references already give.
+    pub fn is_pinned(&self) -> bool {
+        self.version.is_some() || self.identity.is_some()
+    }
+}
+
+/// Gathers the assemblies named by type references, generic arguments included.
+#[derive(Default)]
+pub(crate) struct AssemblyNames<'a>(HashSet<&'a StringName>);
+
+impl<'a> AssemblyNames<'a> {
+    pub(crate) fn into_inner(self) -> HashSet<&'a StringName> {
+        self.0
+    }
+    pub(crate) fn add(&mut self, ty: &'a StringTypeReference) {
+        if let Some((assem, _)) = split_type_ref(ty) {
+            self.0.insert(assem);
+        }
+        if let StringTypeReference::WithGeneric { type_vars, .. } = ty {
+            type_vars.values().for_each(|arg| self.add(arg));
+        }
+    }
+    fn add_method_ref(&mut self, method: &'a StringMethodReference) {
+        if let StringMethodReference::WithGeneric { type_vars, .. } = method {
+            type_vars.values().for_each(|arg| self.add(arg));
+        }
+    }
+    pub(crate) fn add_attributes(&mut self, attrs: &'a [CustomAttribute]) {
+        for attr in attrs {
+            self.add(attr.attr_type());
+            self.add_method_ref(attr.ctor());
+        }
+    }
+    fn add_type_vars(&mut self, type_vars: &'a IndexMap<StringName, GenericBinding>) {
+        for binding in type_vars.values() {
+            binding
+                .implemented_interfaces
+                .iter()
+                .for_each(|ty| self.add(ty));
+            binding.parent.iter().for_each(|ty| self.add(ty));
+        }
+    }
+    fn add_field(&mut self, field: &'a Field) {
+        self.add(&field.ty);
+        self.add_attributes(&field.custom_attributes);
+    }
+    fn add_method(&mut self, method: &'a Method) {
+        self.add(method.ret_type());
+        self.add_type_vars(method.type_vars());
+        self.add_attributes(method.custom_attributes());
+        for param in method.params() {
+            self.add(param.ty());
+            self.add_attributes(param.custom_attributes());
+        }
+        method
+            .locals()
+            .iter()
+            .for_each(|local| self.add(local.ty()));
+        for clause in method.exception_clauses() {
+            if let HandlerKind::Catch(ty) = &clause.kind {
+                self.add(ty);
+            }
+        }
+        for instruction in method.instructions() {
+            match instruction {
+                StringInstruction::LoadStatic { ty, .. } => self.add(ty),
+                StringInstruction::NewObject {
+                    ty,
+                    ctor_name: method,
+                    ..
+                }
+                | StringInstruction::StaticCall { ty, method, .. } => {
+                    self.add(ty);
+                    self.add_method_ref(method);
+                }
+                StringInstruction::InstanceCall { method, .. } => self.add_method_ref(method),
+                _ => {}
+            }
+        }
+    }
+    pub(crate) fn add_type_def(&mut self, type_def: &'a TypeDef) {
+        type_def.parent().into_iter().for_each(|ty| self.add(ty));
+        type_def.interfaces().iter().for_each(|ty| self.add(ty));
+        self.add_attributes(type_def.custom_attributes());
+        type_def
+            .methods()
+            .into_iter()
+            .flatten()
+            .for_each(|(_, method)| self.add_method(method));
+        type_def
+            .fields()
+            .into_iter()
+            .flatten()
+            .for_each(|(_, field)| self.add_field(field));
+        for property in type_def.properties().into_iter().flat_map(IndexMap::values) {
+            self.add(&property.ty);
+            self.add_attributes(&property.custom_attributes);
+            property
+                .getter
+                .iter()
+                .chain(&property.setter)
+                .for_each(|accessor| self.add_method_ref(accessor));
+        }
+        for event in type_def.events().into_iter().flat_map(IndexMap::values) {
+            self.add(&event.ty);
+            self.add_attributes(&event.custom_attributes);
+            [&event.add, &event.remove]
+                .into_iter()
+                .chain(&event.raise)
+                .for_each(|accessor| self.add_method_ref(accessor));
+        }
+        match type_def {
+            TypeDef::Class(class_def) => {
+                self.add_type_vars(&class_def.type_vars);
+                for method_override in &class_def.overrides {
+                    self.add(&method_override.declaring_type);
+                    self.add_method_ref(&method_override.declaration);
+                    self.add_method_ref(&method_override.implementation);
+                }
+            }
+            TypeDef::Struct(struct_def) => self.add_type_vars(&struct_def.type_vars),
+            TypeDef::Interface(interface_def) => self.add_type_vars(&interface_def.type_vars),
+            TypeDef::Delegate(delegate_def) => {
+                self.add_type_vars(&delegate_def.type_vars);
+                delegate_def.args.iter().for_each(|ty| self.add(ty));
+                self.add(&delegate_def.ret_type);
+            }
+            TypeDef::Enum(_) => {}
+        }
+    }
+    pub(crate) fn add_implementation(&mut self, implementation: &'a Implementation) {
+        self.add(&implementation.ty);
+        implementation.interface.iter().for_each(|ty| self.add(ty));
+        implementation
+            .methods
+            .values()
+            .for_each(|method| self.add_method(method));
+    }
+}
@@ -0,0 +1,54 @@
+use crate::ty::constant::ConstantValue;
+use global::derive_ctor::ctor;
+use global::getset::Getters;
+use global::{IndexMap, StringMethodReference, StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+/// User metadata such as `[Obsolete]`, recorded as a constructor call on the attribute type.
+#[derive(ctor, Debug, Clone, PartialEq, Eq, Getters, ReadFromFile, WriteToFile)]
+#[getset(get = "pub")]
+pub struct CustomAttribute {
+    pub(crate) attr_type: StringTypeReference,
+    pub(crate) ctor: StringMethodReference,
+    pub(crate) positional_args: Vec<ConstantValue>,
+    /// Fields or properties of the attribute type assigned after construction.
+    pub(crate) named_args: IndexMap<StringName, ConstantValue>,
+}
+
+/// An item found by [`Assembly::items_with_attribute`](crate::assembly::Assembly::items_with_attribute).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeTarget<'a> {
+    Assembly,
+    Type(&'a StringName),
+    Method {
+        ty: &'a StringName,
+        method: &'a StringName,
+    },
+    Field {
+        ty: &'a StringName,
+        field: &'a StringName,
+    },
+    Property {
+        ty: &'a StringName,
+        property: &'a StringName,
+    },
+    Event {
+        ty: &'a StringName,
+        event: &'a StringName,
+    },
+    Parameter {
+        ty: &'a StringName,
+        method: &'a StringName,
+        index: usize,
+    },
+    /// A method of the implementation block stored under `implementation`.
+    ImplementationMethod {
+        implementation: &'a StringName,
+        method: &'a StringName,
+    },
+    ImplementationParameter {
+        implementation: &'a StringName,
+        method: &'a StringName,
+        index: usize,
+    },
+}
@@ -1,15 +1,29 @@
-use crate::ty::GenericBinding;
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::event::Event;
 use crate::ty::method::Method;
+use crate::ty::nesting::Nesting;
+use crate::ty::overrides::MethodOverride;
+use crate::ty::property::Property;
+use crate::ty::signature::check_method_keys;
+use crate::ty::{GenericBinding, insert_method, validate_members};
+use crate::validation::ValidationError;
 use global::attrs::TypeAttr;
 use global::derive_ctor::ctor;
-use global::getset::{CopyGetters, Getters};
-use global::{IndexMap, StringName, StringTypeReference};
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexMap, StringMethodReference, StringName, StringTypeReference};
 use proc_macros::{ReadFromFile, WriteToFile};
 
-#[derive(ctor, Debug, Clone, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
 #[getset(get = "pub")]
 pub struct ClassDef {
     pub(crate) parent: Option<StringTypeReference>,
+    /// Every interface the type implements, each backed by an
+    /// [`Implementation`](crate::Implementation) unless all its methods have defaults.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) interfaces: Vec<StringTypeReference>,
     pub(crate) type_vars: IndexMap<StringName, GenericBinding>,
     #[getset(skip)]
     #[get_copy = "pub"]
@@ -17,6 +31,86 @@ pub struct ClassDef {
     pub(crate) name: StringName,
     pub(crate) methods: IndexMap<StringName, Method>,
     pub(crate) fields: IndexMap<StringName, Field>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) properties: IndexMap<StringName, Property>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) events: IndexMap<StringName, Event>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+    /// `None` for top-level types.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) nesting: Option<Nesting>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) overrides: Vec<MethodOverride>,
+}
+
+impl ClassDef {
+    /// Inserts `method` under the key derived from its
+    /// [`MethodSignature`](crate::ty::signature::MethodSignature), returning the
+    /// method it replaces.
+    pub fn insert_method(&mut self, method: Method) -> Option<Method> {
+        insert_method(&mut self.methods, method)
+    }
+    /// Checks that every method is stored under its canonical key.
+    pub fn validate_method_keys(&self) -> Result<(), ValidationError> {
+        check_method_keys(&self.name, &self.methods)
+    }
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_members(
+            &self.name,
+            &self.type_vars,
+            &self.methods,
+            &self.properties,
+            &self.events,
+        )?;
+        for (index, method_override) in self.overrides.iter().enumerate() {
+            let implementation = method_override.implementation();
+            match self.methods.get(&implementation.string_name_repr()) {
+                None => {
+                    return Err(ValidationError::UnknownOverrideImplementation {
+                        ty: self.name.clone(),
+                        method: implementation.clone(),
+                    });
+                }
+                Some(method) if !method.is_virtual() => {
+                    return Err(ValidationError::NonVirtualOverride {
+                        ty: self.name.clone(),
+                        method: method.name().clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+            let overrides_same = |other: &MethodOverride| {
+                other.declaring_type == method_override.declaring_type
+                    && other.declaration == method_override.declaration
+            };
+            if self.overrides[..index].iter().any(overrides_same) {
+                return Err(ValidationError::DuplicateOverride {
+                    ty: self.name.clone(),
+                    declaration: method_override.declaration.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+    /// The method of this class that fills the slot of `declaration` on `declaring_type`.
+    pub fn overriding_method(
+        &self,
+        declaring_type: &StringTypeReference,
+        declaration: &StringMethodReference,
+    ) -> Option<&Method> {
+        let method_override = self.overrides.iter().find(|method_override| {
+            method_override.declaring_type == *declaring_type
+                && method_override.declaration == *declaration
+        })?;
+        self.methods
+            .get(&method_override.implementation.string_name_repr())
+    }
 }
 
 pub type Field = super::field::Field;
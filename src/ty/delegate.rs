@@ -0,0 +1,37 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::nesting::Nesting;
+use crate::ty::{GenericBinding, validate_type_vars};
+use crate::validation::ValidationError;
+use global::attrs::TypeAttr;
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexMap, StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+/// A callable type: invoking a value of it takes `args` and produces `ret_type`.
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct DelegateDef {
+    pub(crate) type_vars: IndexMap<StringName, GenericBinding>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) attr: TypeAttr,
+    pub(crate) name: StringName,
+    pub(crate) args: Vec<StringTypeReference>,
+    pub(crate) ret_type: StringTypeReference,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+    /// `None` for top-level types.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) nesting: Option<Nesting>,
+}
+
+impl DelegateDef {
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_type_vars(&self.type_vars, None, true)
+    }
+}
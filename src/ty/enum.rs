@@ -0,0 +1,107 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::nesting::Nesting;
+use crate::validation::ValidationError;
+use global::attrs::TypeAttr;
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexSet, StringName};
+use proc_macros::{ReadFromFile, WriteToFile};
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ReadFromFile, WriteToFile)]
+#[repr(u8)]
+pub enum EnumUnderlyingType {
+    U8,
+    U16,
+    U32,
+    U64,
+    I8,
+    I16,
+    I32,
+    I64,
+}
+
+impl EnumUnderlyingType {
+    pub fn size(self) -> u32 {
+        match self {
+            Self::U8 | Self::I8 => 1,
+            Self::U16 | Self::I16 => 2,
+            Self::U32 | Self::I32 => 4,
+            Self::U64 | Self::I64 => 8,
+        }
+    }
+    pub fn range(self) -> RangeInclusive<i128> {
+        match self {
+            Self::U8 => u8::MIN as i128..=u8::MAX as i128,
+            Self::U16 => u16::MIN as i128..=u16::MAX as i128,
+            Self::U32 => u32::MIN as i128..=u32::MAX as i128,
+            Self::U64 => u64::MIN as i128..=u64::MAX as i128,
+            Self::I8 => i8::MIN as i128..=i8::MAX as i128,
+            Self::I16 => i16::MIN as i128..=i16::MAX as i128,
+            Self::I32 => i32::MIN as i128..=i32::MAX as i128,
+            Self::I64 => i64::MIN as i128..=i64::MAX as i128,
+        }
+    }
+}
+
+#[derive(ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+#[getset(get = "pub")]
+pub struct EnumMember {
+    pub(crate) name: StringName,
+    /// Wide enough for every [`EnumUnderlyingType`]; [`EnumDef::validate`] checks the real range.
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) value: i128,
+}
+
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct EnumDef {
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) attr: TypeAttr,
+    pub(crate) name: StringName,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) underlying: EnumUnderlyingType,
+    pub(crate) members: Vec<EnumMember>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) is_flags: bool,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+    /// `None` for top-level types.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) nesting: Option<Nesting>,
+}
+
+impl EnumDef {
+    pub fn member(&self, name: &StringName) -> Option<&EnumMember> {
+        self.members.iter().find(|member| member.name.eq(name))
+    }
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let range = self.underlying.range();
+        let mut names = IndexSet::with_capacity(self.members.len());
+        for member in &self.members {
+            if !names.insert(&member.name) {
+                return Err(ValidationError::DuplicateEnumMember {
+                    ty: self.name.clone(),
+                    member: member.name.clone(),
+                });
+            }
+            if !range.contains(&member.value) {
+                return Err(ValidationError::EnumValueOutOfRange {
+                    ty: self.name.clone(),
+                    member: member.name.clone(),
+                    value: member.value,
+                    underlying: self.underlying,
+                });
+            }
+        }
+        Ok(())
+    }
+}
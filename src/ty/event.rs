@@ -0,0 +1,52 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::method::Method;
+use crate::ty::property::check_accessor;
+use crate::validation::ValidationError;
+use enumflags2::{BitFlags, bitflags};
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexMap, StringMethodReference, StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EventImplementationFlags {
+    Static,
+}
+
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct Event {
+    pub(crate) name: StringName,
+    /// The delegate type handlers must have.
+    pub(crate) ty: StringTypeReference,
+    pub(crate) add: StringMethodReference,
+    pub(crate) remove: StringMethodReference,
+    pub(crate) raise: Option<StringMethodReference>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) impl_flags: BitFlags<EventImplementationFlags>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+}
+
+impl Event {
+    /// Checks that the accessors name methods declared on the type `ty`.
+    pub fn validate(
+        &self,
+        ty: &StringName,
+        methods: &IndexMap<StringName, Method>,
+    ) -> Result<(), ValidationError> {
+        for accessor in [&self.add, &self.remove]
+            .into_iter()
+            .chain(self.raise.iter())
+        {
+            check_accessor(ty, &self.name, accessor, methods)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters};
+use global::{StringTypeReference, WithType};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+/// Half-open range `start..end` of instruction indices.
+#[derive(ctor, Debug, Clone, Copy, PartialEq, Eq, Hash, CopyGetters, ReadFromFile, WriteToFile)]
+#[getset(get_copy = "pub")]
+pub struct InstructionRange {
+    pub(crate) start: u64,
+    pub(crate) end: u64,
+}
+
+impl InstructionRange {
+    pub fn is_empty(&self) -> bool {
+        self.start >= self.end
+    }
+    pub fn contains(&self, other: &Self) -> bool {
+        self.start <= other.start && other.end <= self.end
+    }
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.end <= other.start || other.end <= self.start
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, WithType, ReadFromFile, WriteToFile)]
+#[with_type(repr = u8)]
+#[with_type(derive = (Clone, Copy, ReadFromFile, WriteToFile))]
+pub enum HandlerKind {
+    /// Runs when the thrown object is assignable to the type.
+    Catch(StringTypeReference),
+    /// Runs when the filter code leaves `true` in its return register.
+    Filter(InstructionRange),
+    Finally,
+    /// Like `Finally`, but only when the protected range exits by an exception.
+    Fault,
+}
+
+#[derive(ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+#[getset(get = "pub")]
+pub struct ExceptionClause {
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) try_range: InstructionRange,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) handler_range: InstructionRange,
+    pub(crate) kind: HandlerKind,
+}
+
+impl ExceptionClause {
+    /// The try, handler and filter ranges of this clause.
+    pub fn ranges(&self) -> impl Iterator<Item = InstructionRange> {
+        let filter = match self.kind {
+            HandlerKind::Filter(filter) => Some(filter),
+            _ => None,
+        };
+        [self.try_range, self.handler_range]
+            .into_iter()
+            .chain(filter)
+    }
+}
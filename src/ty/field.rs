@@ -1,10 +1,14 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::constant::ConstantValue;
 use global::attrs::FieldAttr;
 use global::derive_ctor::ctor;
-use global::getset::{CopyGetters, Getters};
+use global::getset::{CopyGetters, Getters, MutGetters};
 use global::{StringName, StringTypeReference};
 use proc_macros::{ReadFromFile, WriteToFile};
 
-#[derive(Clone, Debug, Getters, CopyGetters, ReadFromFile, WriteToFile, ctor)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile, ctor,
+)]
 #[getset(get = "pub")]
 pub struct Field {
     pub(crate) name: StringName,
@@ -12,4 +16,17 @@ pub struct Field {
     #[get_copy = "pub"]
     pub(crate) attr: FieldAttr,
     pub(crate) ty: StringTypeReference,
+    /// Value of a `const` field, or the initial value of a static one.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) constant: Option<ConstantValue>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+    /// Byte offset inside an explicitly laid out struct.
+    #[ctor(default)]
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    pub(crate) offset: Option<u32>,
 }
@@ -0,0 +1,50 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::method::Method;
+use crate::ty::nesting::Nesting;
+use crate::ty::{GenericBinding, validate_methods, validate_type_vars};
+use crate::validation::ValidationError;
+use global::attrs::TypeAttr;
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexMap, StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct InterfaceDef {
+    pub(crate) super_interfaces: Vec<StringTypeReference>,
+    pub(crate) type_vars: IndexMap<StringName, GenericBinding>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) attr: TypeAttr,
+    pub(crate) name: StringName,
+    /// Methods flagged [`Abstract`](crate::ty::overrides::SlotFlags::Abstract) are signatures for implementations to provide;
+    /// the others are default implementations, even with an empty body.
+    pub(crate) methods: IndexMap<StringName, Method>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+    /// `None` for top-level types.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) nesting: Option<Nesting>,
+}
+
+impl InterfaceDef {
+    pub fn abstract_methods(&self) -> impl Iterator<Item = (&StringName, &Method)> {
+        self.methods
+            .iter()
+            .filter(|(_, method)| method.is_abstract())
+    }
+    pub fn default_methods(&self) -> impl Iterator<Item = (&StringName, &Method)> {
+        self.methods
+            .iter()
+            .filter(|(_, method)| !method.is_abstract())
+    }
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_type_vars(&self.type_vars, None, true)?;
+        validate_methods(&self.type_vars, &self.methods)
+    }
+}
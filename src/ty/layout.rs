@@ -0,0 +1,62 @@
+use global::derive_ctor::ctor;
+use global::getset::CopyGetters;
+use proc_macros::{ReadFromFile, WriteToFile};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, ReadFromFile, WriteToFile)]
+#[repr(u8)]
+pub enum LayoutKind {
+    /// The runtime may reorder fields.
+    #[default]
+    Auto,
+    /// Fields are laid out in declaration order.
+    Sequential,
+    /// Every instance field carries its own byte offset.
+    Explicit,
+}
+
+#[derive(
+    ctor, Debug, Default, Clone, Copy, PartialEq, Eq, CopyGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get_copy = "pub")]
+pub struct StructLayout {
+    pub(crate) kind: LayoutKind,
+    /// Field alignment in bytes; `None` uses the natural alignment.
+    pub(crate) packing: Option<u16>,
+    /// Total size in bytes; `None` lets the runtime compute it from the fields.
+    pub(crate) size: Option<u32>,
+}
+
+// The core library is not available to this crate, so layout validation only knows the
+// deliberate subset of core types below, laid out for a 64-bit target. Fields of any other
+// core type are treated as opaque: their overlaps are not checked and
+// `ValidationWarning::UnknownFieldLayout` reports them instead.
+
+/// Size in bytes of a primitive value type of the core library, by type name.
+pub(crate) fn core_value_size(ty: &str) -> Option<u32> {
+    Some(match ty {
+        "System.Boolean" | "System.Byte" | "System.SByte" => 1,
+        "System.Char" | "System.Int16" | "System.UInt16" => 2,
+        "System.Int32" | "System.UInt32" | "System.Single" => 4,
+        "System.Int64" | "System.UInt64" | "System.Double" => 8,
+        "System.IntPtr" | "System.UIntPtr" => POINTER_SIZE,
+        _ => return None,
+    })
+}
+
+/// Whether the core library type `ty` is a reference type, so that fields of it hold a
+/// pointer.
+pub(crate) fn is_core_reference_type(ty: &str) -> bool {
+    matches!(
+        ty,
+        "System.Object"
+            | "System.String"
+            | "System.Array"
+            | "System.Array`1"
+            | "System.Delegate"
+            | "System.Exception"
+            | "System.Type"
+    )
+}
+
+/// Size of a reference or native integer on the 64-bit target the sizes above assume.
+pub(crate) const POINTER_SIZE: u32 = 8;
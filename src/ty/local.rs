@@ -0,0 +1,26 @@
+use enumflags2::{BitFlags, bitflags};
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters};
+use global::{StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LocalFlags {
+    /// The referenced object must not be moved while the method runs.
+    Pinned,
+    ByRef,
+}
+
+/// Declared type of one register of a method.
+#[derive(ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+#[getset(get = "pub")]
+pub struct LocalVariable {
+    pub(crate) ty: StringTypeReference,
+    /// Name shown by debuggers; not needed to run the method.
+    pub(crate) name: Option<StringName>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) flags: BitFlags<LocalFlags>,
+}
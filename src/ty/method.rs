@@ -1,15 +1,25 @@
 use crate::ty::GenericBinding;
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::exception::ExceptionClause;
+use crate::ty::local::LocalVariable;
+use crate::ty::overrides::SlotFlags;
+use crate::ty::parameter::Parameter;
+use crate::ty::registers::required_registers;
+use crate::validation::{ValidationError, ValidationWarning};
+use enumflags2::BitFlags;
 use global::attrs::MethodAttr;
 use global::derive_ctor::ctor;
-use global::getset::{CopyGetters, Getters};
+use global::getset::{CopyGetters, Getters, MutGetters};
 use global::instruction::StringInstruction;
 use global::{IndexMap, StringName, StringTypeReference};
 use proc_macros::{ReadFromFile, WriteToFile};
 
-#[derive(Clone, Debug, Getters, CopyGetters, ctor, ReadFromFile, WriteToFile)]
+#[derive(
+    Clone, Debug, PartialEq, Eq, Getters, CopyGetters, MutGetters, ctor, ReadFromFile, WriteToFile,
+)]
 #[allow(unused)]
 #[getset(get = "pub")]
-#[ctor(pub new)]
+#[ctor(pub with_params)]
 pub struct Method {
     name: StringName,
     #[getset(skip)]
@@ -17,6 +27,152 @@ pub struct Method {
     attr: MethodAttr,
     instructions: Vec<StringInstruction>,
     ret_type: StringTypeReference,
-    args: Vec<StringTypeReference>,
+    #[getset(get_mut = "pub")]
+    params: Vec<Parameter>,
+    #[getset(get_mut = "pub")]
     type_vars: IndexMap<StringName, GenericBinding>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    custom_attributes: Vec<CustomAttribute>,
+    /// Entry `i` declares register `i`; registers past the end are untyped.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    locals: Vec<LocalVariable>,
+    /// Inner clauses come before the clauses that enclose them; clauses protecting the same
+    /// range are tried in order.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    exception_clauses: Vec<ExceptionClause>,
+    #[ctor(default)]
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    slot_flags: BitFlags<SlotFlags>,
+}
+
+impl Method {
+    /// Builds a method whose parameters carry only their types; see [`Method::with_params`]
+    /// for names, modifiers and defaults.
+    pub fn new(
+        name: StringName,
+        attr: MethodAttr,
+        instructions: Vec<StringInstruction>,
+        ret_type: StringTypeReference,
+        args: Vec<StringTypeReference>,
+        type_vars: IndexMap<StringName, GenericBinding>,
+    ) -> Self {
+        let params = args.into_iter().map(Parameter::unnamed).collect();
+        Self::with_params(name, attr, instructions, ret_type, params, type_vars)
+    }
+    pub fn args(&self) -> impl Iterator<Item = &StringTypeReference> {
+        self.params.iter().map(Parameter::ty)
+    }
+    pub fn local(&self, register: u64) -> Option<&LocalVariable> {
+        self.locals.get(usize::try_from(register).ok()?)
+    }
+    pub fn is_virtual(&self) -> bool {
+        self.slot_flags.contains(SlotFlags::Virtual)
+    }
+    pub fn is_abstract(&self) -> bool {
+        self.slot_flags.contains(SlotFlags::Abstract)
+    }
+    /// The smallest `register_len` that covers the instructions; see [`registers`](crate::registers).
+    pub fn required_registers(&self) -> Option<u64> {
+        required_registers(&self.instructions)
+    }
+    /// Warns when `register_len` exceeds what the instructions and declared locals need.
+    pub fn register_warning(&self) -> Option<ValidationWarning> {
+        let register_len = self.attr.register_len();
+        let required = self.required_registers()?.max(self.locals.len() as u64);
+        (register_len > required).then(|| ValidationWarning::ExcessRegisters {
+            method: self.name.clone(),
+            register_len,
+            required,
+        })
+    }
+    /// Checks the slot flags, that abstract methods have no body, that the instructions and
+    /// every declared local fit in the method's registers and that the exception clauses are
+    /// in bounds and well-nested.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        if !self.is_virtual()
+            && self
+                .slot_flags
+                .intersects(SlotFlags::NewSlot | SlotFlags::Final)
+        {
+            return Err(ValidationError::SlotFlagsWithoutVirtual {
+                method: self.name.clone(),
+            });
+        }
+        if self.is_abstract() && !self.instructions.is_empty() {
+            return Err(ValidationError::AbstractMethodWithBody {
+                method: self.name.clone(),
+            });
+        }
+        let register_len = self.attr.register_len();
+        let Some(required) = self.required_registers() else {
+            return Err(ValidationError::RegisterOutOfRange {
+                method: self.name.clone(),
+            });
+        };
+        if required > register_len {
+            return Err(ValidationError::TooFewRegisters {
+                method: self.name.clone(),
+                register_len,
+                required,
+            });
+        }
+        if self.locals.len() as u64 > register_len {
+            return Err(ValidationError::TooManyLocals {
+                method: self.name.clone(),
+                locals: self.locals.len(),
+                register_len,
+            });
+        }
+        self.validate_exception_clauses()
+    }
+    fn validate_exception_clauses(&self) -> Result<(), ValidationError> {
+        let overlap = |first, second| ValidationError::ExceptionRangesOverlap {
+            method: self.name.clone(),
+            first,
+            second,
+        };
+        let len = self.instructions.len() as u64;
+        let mut ranges = Vec::new();
+        for (clause_index, clause) in self.exception_clauses.iter().enumerate() {
+            let clause_ranges = clause.ranges().collect::<Vec<_>>();
+            for (index, range) in clause_ranges.iter().enumerate() {
+                if range.is_empty() || range.end > len {
+                    return Err(ValidationError::ExceptionRangeOutOfBounds {
+                        method: self.name.clone(),
+                        range: *range,
+                        len,
+                    });
+                }
+                if let Some(other) = clause_ranges[..index]
+                    .iter()
+                    .find(|other| !range.is_disjoint(other))
+                {
+                    return Err(overlap(*other, *range));
+                }
+            }
+            ranges.extend(clause_ranges.into_iter().map(|range| (clause_index, range)));
+        }
+        for (index, (clause, range)) in ranges.iter().enumerate() {
+            if let Some((_, other)) = ranges[..index].iter().find(|(_, other)| {
+                !range.is_disjoint(other) && !range.contains(other) && !other.contains(range)
+            }) {
+                return Err(overlap(*other, *range));
+            }
+            if let Some((_, outer)) = ranges[..index].iter().find(|(other_clause, other)| {
+                other_clause != clause && other != range && other.contains(range)
+            }) {
+                return Err(ValidationError::ExceptionClausesOutOfOrder {
+                    method: self.name.clone(),
+                    outer: *outer,
+                    inner: *range,
+                });
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,25 @@
+use global::StringName;
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+/// Who may see a type declared inside another type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, ReadFromFile, WriteToFile)]
+#[repr(u8)]
+pub enum NestedVisibility {
+    Public,
+    /// Only the enclosing type and the types nested in it.
+    Private,
+    /// Every type of the declaring assembly.
+    Assembly,
+}
+
+/// Marks a type as declared inside `enclosing`, a key of the same assembly's `type_defs`.
+#[derive(ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+#[getset(get = "pub")]
+pub struct Nesting {
+    pub(crate) enclosing: StringName,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) visibility: NestedVisibility,
+}
@@ -0,0 +1,30 @@
+use enumflags2::bitflags;
+use global::derive_ctor::ctor;
+use global::getset::Getters;
+use global::{StringMethodReference, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+/// How a method takes part in virtual dispatch.
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SlotFlags {
+    Virtual,
+    /// Gets a vtable slot of its own instead of reusing the slot of a base method with the
+    /// same signature.
+    NewSlot,
+    /// Derived types cannot override it further.
+    Final,
+    /// Declares a signature without a body, for implementing types to provide.
+    Abstract,
+}
+
+/// States that `implementation`, a method of the declaring class, fills the slot of
+/// `declaration` on `declaring_type`.
+#[derive(ctor, Debug, Clone, PartialEq, Eq, Getters, ReadFromFile, WriteToFile)]
+#[getset(get = "pub")]
+pub struct MethodOverride {
+    pub(crate) declaring_type: StringTypeReference,
+    pub(crate) declaration: StringMethodReference,
+    pub(crate) implementation: StringMethodReference,
+}
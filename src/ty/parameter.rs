@@ -0,0 +1,41 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::constant::ConstantValue;
+use enumflags2::{BitFlags, bitflags};
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ParameterFlags {
+    Ref,
+    Out,
+    /// Trailing arguments are collected into an array.
+    Params,
+}
+
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct Parameter {
+    /// Empty for parameters that were declared without a name.
+    pub(crate) name: StringName,
+    pub(crate) ty: StringTypeReference,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) flags: BitFlags<ParameterFlags>,
+    /// Value used when a caller omits the argument.
+    pub(crate) default: Option<ConstantValue>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+}
+
+impl Parameter {
+    pub fn unnamed(ty: StringTypeReference) -> Self {
+        Self::new(StringName::default(), ty, BitFlags::empty(), None)
+    }
+}
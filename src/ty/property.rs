@@ -0,0 +1,63 @@
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::method::Method;
+use crate::validation::ValidationError;
+use enumflags2::{BitFlags, bitflags};
+use global::derive_ctor::ctor;
+use global::getset::{CopyGetters, Getters, MutGetters};
+use global::{IndexMap, StringMethodReference, StringName, StringTypeReference};
+use proc_macros::{ReadFromFile, WriteToFile};
+
+#[bitflags]
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PropertyImplementationFlags {
+    Static,
+}
+
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
+#[getset(get = "pub")]
+pub struct Property {
+    pub(crate) name: StringName,
+    pub(crate) ty: StringTypeReference,
+    pub(crate) getter: Option<StringMethodReference>,
+    pub(crate) setter: Option<StringMethodReference>,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    pub(crate) impl_flags: BitFlags<PropertyImplementationFlags>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+}
+
+impl Property {
+    /// Checks that the accessors name methods declared on the type `ty`.
+    pub fn validate(
+        &self,
+        ty: &StringName,
+        methods: &IndexMap<StringName, Method>,
+    ) -> Result<(), ValidationError> {
+        for accessor in self.getter.iter().chain(self.setter.iter()) {
+            check_accessor(ty, &self.name, accessor, methods)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn check_accessor(
+    ty: &StringName,
+    member: &StringName,
+    accessor: &StringMethodReference,
+    methods: &IndexMap<StringName, Method>,
+) -> Result<(), ValidationError> {
+    if methods.contains_key(&accessor.string_name_repr()) {
+        Ok(())
+    } else {
+        Err(ValidationError::MissingAccessor {
+            ty: ty.clone(),
+            member: member.clone(),
+            accessor: accessor.clone(),
+        })
+    }
+}
@@ -0,0 +1,56 @@
+//! Which registers a method body touches.
+//!
+//! Every register an instruction names counts, whether it is read or written: `register_addr`
+//! of the load, store and return instructions, the `args` of calls and constructions, the
+//! `val` an instance call is made on and the `ret_at` a call result is written to. `LoadArg`'s
+//! `arg` is an argument index, not a register.
+
+use global::instruction::StringInstruction;
+
+/// The registers `instruction` names, in operand order.
+#[allow(deprecated)]
+pub fn instruction_registers(instruction: &StringInstruction) -> Vec<u64> {
+    use StringInstruction::*;
+    match instruction {
+        LoadTrue { register_addr }
+        | LoadFalse { register_addr }
+        | Load_u8 { register_addr, .. }
+        | Load_u8_0 { register_addr }
+        | Load_u8_1 { register_addr }
+        | Load_u8_2 { register_addr }
+        | Load_u8_3 { register_addr }
+        | Load_u8_4 { register_addr }
+        | Load_u8_5 { register_addr }
+        | Load_u64 { register_addr, .. }
+        | LoadArg { register_addr, .. }
+        | LoadAllArgsAsArray { register_addr }
+        | LoadStatic { register_addr, .. }
+        | ReturnVal { register_addr }
+        | SetField { register_addr, .. } => vec![*register_addr],
+        NewObject {
+            args,
+            register_addr,
+            ..
+        } => args.iter().chain([register_addr]).copied().collect(),
+        InstanceCall {
+            val, args, ret_at, ..
+        } => [val]
+            .into_iter()
+            .chain(args)
+            .chain([ret_at])
+            .copied()
+            .collect(),
+        StaticCall { args, ret_at, .. } => args.iter().chain([ret_at]).copied().collect(),
+    }
+}
+
+/// One more than the highest register named by `instructions`, or 0 if they name none.
+///
+/// `None` when they name register `u64::MAX`, which no `register_len` can cover.
+pub fn required_registers(instructions: &[StringInstruction]) -> Option<u64> {
+    instructions
+        .iter()
+        .flat_map(instruction_registers)
+        .max()
+        .map_or(Some(0), |register| register.checked_add(1))
+}
@@ -0,0 +1,94 @@
+use crate::ty::method::Method;
+use crate::ty::parameter::{Parameter, ParameterFlags};
+use crate::validation::ValidationError;
+use global::StringName;
+use global::getset::{CopyGetters, Getters};
+use std::fmt::{self, Display, Formatter};
+
+/// The parts of a method that tell overloads apart, and the canonical key derived from them:
+/// ``Name`Arity(Param,ref Param,out Param)``, with the arity omitted when it is zero.
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+#[getset(get = "pub")]
+pub struct MethodSignature {
+    name: StringName,
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    generic_arity: usize,
+    /// Unnamed, without defaults or attributes, and with only the `Ref` and `Out` flags.
+    params: Vec<Parameter>,
+}
+
+impl MethodSignature {
+    /// `name` is the bare method name; anything from the first `` ` `` or `(` on is dropped,
+    /// so an existing key can be passed as well. Of each parameter only the type and the
+    /// `Ref` and `Out` modifiers are kept.
+    pub fn new(
+        name: &StringName,
+        generic_arity: usize,
+        params: impl IntoIterator<Item = Parameter>,
+    ) -> Self {
+        let name = name.as_str();
+        let bare = &name[..name.find(['`', '(']).unwrap_or(name.len())];
+        let by_ref = ParameterFlags::Ref | ParameterFlags::Out;
+        Self {
+            name: StringName::from_string(bare.to_owned()),
+            generic_arity,
+            params: params
+                .into_iter()
+                .map(|param| {
+                    Parameter::new(StringName::default(), param.ty, param.flags & by_ref, None)
+                })
+                .collect(),
+        }
+    }
+    pub fn of(method: &Method) -> Self {
+        Self::new(
+            method.name(),
+            method.type_vars().len(),
+            method.params().iter().cloned(),
+        )
+    }
+    pub fn key(&self) -> StringName {
+        StringName::from_string(self.to_string())
+    }
+}
+
+impl Display for MethodSignature {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name.as_str())?;
+        if self.generic_arity != 0 {
+            write!(f, "`{}", self.generic_arity)?;
+        }
+        f.write_str("(")?;
+        for (index, param) in self.params.iter().enumerate() {
+            if index != 0 {
+                f.write_str(",")?;
+            }
+            if param.flags().contains(ParameterFlags::Ref) {
+                f.write_str("ref ")?;
+            } else if param.flags().contains(ParameterFlags::Out) {
+                f.write_str("out ")?;
+            }
+            f.write_str(param.ty().string_name_repr().as_str())?;
+        }
+        f.write_str(")")
+    }
+}
+
+/// Reports the first entry of `methods` whose key is not the canonical key of its method.
+pub(crate) fn check_method_keys<'a>(
+    ty: &StringName,
+    methods: impl IntoIterator<Item = (&'a StringName, &'a Method)>,
+) -> Result<(), ValidationError> {
+    for (key, method) in methods {
+        let expected = MethodSignature::of(method).key();
+        if *key != expected {
+            return Err(ValidationError::MethodKeyMismatch {
+                ty: ty.clone(),
+                key: key.clone(),
+                expected,
+            });
+        }
+    }
+    Ok(())
+}
@@ -1,15 +1,29 @@
-use crate::ty::GenericBinding;
+use crate::ty::attribute::CustomAttribute;
+use crate::ty::event::Event;
+use crate::ty::layout::{LayoutKind, StructLayout};
 use crate::ty::method::Method;
-use global::attrs::TypeAttr;
+use crate::ty::nesting::Nesting;
+use crate::ty::property::Property;
+use crate::ty::signature::check_method_keys;
+use crate::ty::{GenericBinding, insert_method, validate_members};
+use crate::validation::ValidationError;
+use global::attrs::{FieldImplementationFlags, TypeAttr};
 use global::derive_ctor::ctor;
-use global::getset::{CopyGetters, Getters};
+use global::getset::{CopyGetters, Getters, MutGetters};
 use global::{IndexMap, StringName, StringTypeReference};
 use proc_macros::{ReadFromFile, WriteToFile};
 
-#[derive(ctor, Debug, Clone, Getters, CopyGetters, ReadFromFile, WriteToFile)]
+#[derive(
+    ctor, Debug, Clone, PartialEq, Eq, Getters, CopyGetters, MutGetters, ReadFromFile, WriteToFile,
+)]
 #[getset(get = "pub")]
 pub struct StructDef {
     pub(crate) parent: Option<StringTypeReference>,
+    /// Every interface the type implements, each backed by an
+    /// [`Implementation`](crate::Implementation) unless all its methods have defaults.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) interfaces: Vec<StringTypeReference>,
     pub(crate) type_vars: IndexMap<StringName, GenericBinding>,
     #[getset(skip)]
     #[get_copy = "pub"]
@@ -17,6 +31,74 @@ pub struct StructDef {
     pub(crate) name: StringName,
     pub(crate) methods: IndexMap<StringName, Method>,
     pub(crate) fields: IndexMap<StringName, Field>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) properties: IndexMap<StringName, Property>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) events: IndexMap<StringName, Event>,
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) custom_attributes: Vec<CustomAttribute>,
+    /// `None` for top-level types.
+    #[ctor(default)]
+    #[getset(get_mut = "pub")]
+    pub(crate) nesting: Option<Nesting>,
+    #[ctor(default)]
+    #[getset(skip)]
+    #[get_copy = "pub"]
+    #[get_mut = "pub"]
+    pub(crate) layout: StructLayout,
+}
+
+impl StructDef {
+    /// Inserts `method` under the key derived from its
+    /// [`MethodSignature`](crate::ty::signature::MethodSignature), returning the
+    /// method it replaces.
+    pub fn insert_method(&mut self, method: Method) -> Option<Method> {
+        insert_method(&mut self.methods, method)
+    }
+    /// Checks that every method is stored under its canonical key.
+    pub fn validate_method_keys(&self) -> Result<(), ValidationError> {
+        check_method_keys(&self.name, &self.methods)
+    }
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        validate_members(
+            &self.name,
+            &self.type_vars,
+            &self.methods,
+            &self.properties,
+            &self.events,
+        )?;
+        let explicit = self.layout.kind() == LayoutKind::Explicit;
+        for field in self.instance_fields() {
+            match (explicit, field.offset()) {
+                (true, None) => {
+                    return Err(ValidationError::MissingFieldOffset {
+                        ty: self.name.clone(),
+                        field: field.name().clone(),
+                    });
+                }
+                (false, Some(_)) => {
+                    return Err(ValidationError::UnexpectedFieldOffset {
+                        ty: self.name.clone(),
+                        field: field.name().clone(),
+                    });
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+    /// Fields that take up space in each instance, i.e. the non-static ones.
+    pub fn instance_fields(&self) -> impl Iterator<Item = &Field> {
+        self.fields.values().filter(|field| {
+            !field
+                .attr()
+                .impl_flags()
+                .contains(FieldImplementationFlags::Static)
+        })
+    }
 }
 
 pub type Field = super::field::Field;
@@ -0,0 +1,239 @@
+use crate::ty::r#enum::EnumUnderlyingType;
+use crate::ty::exception::InstructionRange;
+use global::{StringMethodReference, StringName, StringTypeReference};
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationError {
+    #[error("enum `{}` declares member `{}` more than once", .ty.as_str(), .member.as_str())]
+    DuplicateEnumMember { ty: StringName, member: StringName },
+    #[error(
+        "value {value} of enum member `{}::{}` does not fit in {underlying:?}",
+        .ty.as_str(),
+        .member.as_str()
+    )]
+    EnumValueOutOfRange {
+        ty: StringName,
+        member: StringName,
+        value: i128,
+        underlying: EnumUnderlyingType,
+    },
+    #[error(
+        "accessor `{}` of `{}::{}` is not a method of the type",
+        .accessor.string_name_repr().as_str(),
+        .ty.as_str(),
+        .member.as_str()
+    )]
+    MissingAccessor {
+        ty: StringName,
+        member: StringName,
+        accessor: StringMethodReference,
+    },
+    #[error(
+        "method `{}` declares {locals} locals but only has {register_len} registers",
+        .method.as_str()
+    )]
+    TooManyLocals {
+        method: StringName,
+        locals: usize,
+        register_len: u64,
+    },
+    #[error(
+        "method `{}` uses {required} registers but only declares {register_len}",
+        .method.as_str()
+    )]
+    TooFewRegisters {
+        method: StringName,
+        register_len: u64,
+        required: u64,
+    },
+    #[error("method `{}` names register {}", .method.as_str(), u64::MAX)]
+    RegisterOutOfRange { method: StringName },
+    #[error(
+        "exception range {range:?} of method `{}` is empty or exceeds its {len} instructions",
+        .method.as_str()
+    )]
+    ExceptionRangeOutOfBounds {
+        method: StringName,
+        range: InstructionRange,
+        len: u64,
+    },
+    #[error(
+        "exception ranges {first:?} and {second:?} of method `{}` overlap without nesting",
+        .method.as_str()
+    )]
+    ExceptionRangesOverlap {
+        method: StringName,
+        first: InstructionRange,
+        second: InstructionRange,
+    },
+    #[error(
+        "exception clause range {outer:?} of method `{}` is listed before {inner:?}, which it encloses",
+        .method.as_str()
+    )]
+    ExceptionClausesOutOfOrder {
+        method: StringName,
+        outer: InstructionRange,
+        inner: InstructionRange,
+    },
+    #[error("assembly `{}` is not referenced and cannot be pinned", .assembly.as_str())]
+    UnreferencedAssembly { assembly: StringName },
+    #[error("enclosing type `{}` of `{}` does not exist", .enclosing.as_str(), .ty.as_str())]
+    UnknownEnclosingType {
+        ty: StringName,
+        enclosing: StringName,
+    },
+    #[error(
+        "`{}` cannot enclose the nested type `{}`",
+        .enclosing.as_str(),
+        .ty.as_str()
+    )]
+    EnclosingTypeCannotNest {
+        ty: StringName,
+        enclosing: StringName,
+    },
+    #[error("type `{}` is nested inside itself", .ty.as_str())]
+    NestingCycle { ty: StringName },
+    #[error("only interface and delegate type variables may be variant, not `{}`", .var.as_str())]
+    VarianceNotAllowed { var: StringName },
+    #[error(
+        "type variable `{}` cannot be both a reference type and a value type",
+        .var.as_str()
+    )]
+    ConflictingGenericConstraints { var: StringName },
+    #[error("type variable `{}` is constrained to itself through other variables", .var.as_str())]
+    TypeVarConstraintCycle { var: StringName },
+    #[error(
+        "type variable `{}` is constrained by `{}`, which is not another type variable in scope",
+        .var.as_str(),
+        .constraint.as_str()
+    )]
+    InvalidTypeVarConstraint {
+        var: StringName,
+        constraint: StringName,
+    },
+    #[error("type `{}` is not defined in this assembly", .ty.string_name_repr().as_str())]
+    UnknownType { ty: StringTypeReference },
+    #[error("`{}` is not an interface", .ty.string_name_repr().as_str())]
+    NotAnInterface { ty: StringTypeReference },
+    #[error(
+        "implementation `{}` does not implement interface method `{}`",
+        .implementation.as_str(),
+        .method.as_str()
+    )]
+    MissingInterfaceMethod {
+        implementation: StringName,
+        method: StringName,
+    },
+    #[error(
+        "field `{}::{}` needs an offset because the struct has an explicit layout",
+        .ty.as_str(),
+        .field.as_str()
+    )]
+    MissingFieldOffset { ty: StringName, field: StringName },
+    #[error(
+        "field `{}::{}` has an offset but the struct layout is not explicit",
+        .ty.as_str(),
+        .field.as_str()
+    )]
+    UnexpectedFieldOffset { ty: StringName, field: StringName },
+    #[error(
+        "field `{}::{}` ends at byte {end}, past the declared struct size {size}",
+        .ty.as_str(),
+        .field.as_str()
+    )]
+    FieldExceedsStructSize {
+        ty: StringName,
+        field: StringName,
+        end: u64,
+        size: u32,
+    },
+    #[error(
+        "reference-type field `{}::{}` overlaps field `{}`",
+        .ty.as_str(),
+        .field.as_str(),
+        .other.as_str()
+    )]
+    OverlappingReferenceField {
+        ty: StringName,
+        field: StringName,
+        other: StringName,
+    },
+    #[error("method `{}` is new-slot or final without being virtual", .method.as_str())]
+    SlotFlagsWithoutVirtual { method: StringName },
+    #[error("abstract method `{}` has instructions", .method.as_str())]
+    AbstractMethodWithBody { method: StringName },
+    #[error(
+        "override implementation `{}` is not a method of `{}`",
+        .method.string_name_repr().as_str(),
+        .ty.as_str()
+    )]
+    UnknownOverrideImplementation {
+        ty: StringName,
+        method: StringMethodReference,
+    },
+    #[error(
+        "method `{}::{}` overrides a slot but is not virtual",
+        .ty.as_str(),
+        .method.as_str()
+    )]
+    NonVirtualOverride { ty: StringName, method: StringName },
+    #[error(
+        "`{}` overrides `{}` more than once",
+        .ty.as_str(),
+        .declaration.string_name_repr().as_str()
+    )]
+    DuplicateOverride {
+        ty: StringName,
+        declaration: StringMethodReference,
+    },
+    #[error(
+        "implementation `{}` implements `{}`, which its type does not declare",
+        .implementation.as_str(),
+        .interface.string_name_repr().as_str()
+    )]
+    UndeclaredInterfaceImplementation {
+        implementation: StringName,
+        interface: StringTypeReference,
+    },
+    #[error(
+        "`{}` declares interface `{}` but no implementation of it",
+        .ty.as_str(),
+        .interface.string_name_repr().as_str()
+    )]
+    MissingInterfaceImplementation {
+        ty: StringName,
+        interface: StringTypeReference,
+    },
+    #[error(
+        "method of `{}` is stored under `{}` but its signature gives `{}`",
+        .ty.as_str(),
+        .key.as_str(),
+        .expected.as_str()
+    )]
+    MethodKeyMismatch {
+        ty: StringName,
+        key: StringName,
+        expected: StringName,
+    },
+}
+
+/// Metadata that is valid but probably not what was meant.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ValidationWarning {
+    #[error(
+        "method `{}` declares {register_len} registers but only uses {required}",
+        .method.as_str()
+    )]
+    ExcessRegisters {
+        method: StringName,
+        register_len: u64,
+        required: u64,
+    },
+    #[error(
+        "the size or reference-ness of field `{}::{}` cannot be determined, so its overlaps \
+         are not checked",
+        .ty.as_str(),
+        .field.as_str()
+    )]
+    UnknownFieldLayout { ty: StringName, field: StringName },
+}